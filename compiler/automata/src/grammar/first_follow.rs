@@ -0,0 +1,355 @@
+use crate::grammar::{Grammar, GrammarError, NonTerminal, Symbol, Terminal};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Terminal that may follow a nonterminal, or the end of the input.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Lookahead {
+    /// The given terminal.
+    Terminal(Terminal),
+    /// The end of the input, written `$`.
+    End,
+}
+
+impl fmt::Display for Lookahead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Terminal(terminal) => write!(f, "{}", terminal),
+            Self::End => write!(f, "$"),
+        }
+    }
+}
+
+/// Nullable nonterminals, FIRST sets and FOLLOW sets of a context-free grammar.
+#[derive(Clone, Debug)]
+pub struct FirstFollow {
+    /// The start symbol and the nonterminals with productions, in definition order.
+    nonterminals: Vec<NonTerminal>,
+    /// Nonterminals that can derive the empty sequence.
+    nullable: BTreeSet<NonTerminal>,
+    /// Terminals that can start a sequence derived from each nonterminal.
+    first: BTreeMap<NonTerminal, BTreeSet<Terminal>>,
+    /// Terminals that can follow each nonterminal in a sequence derived from the start symbol.
+    follow: BTreeMap<NonTerminal, BTreeSet<Lookahead>>,
+}
+
+impl FirstFollow {
+    /// Returns whether `nonterminal` can derive the empty sequence.
+    pub fn is_nullable(&self, nonterminal: &NonTerminal) -> bool {
+        self.nullable.contains(nonterminal)
+    }
+
+    /// Returns whether `symbols` can derive the empty sequence.
+    pub fn is_nullable_sequence(&self, symbols: &[Symbol]) -> bool {
+        symbols.iter().all(|symbol| match symbol {
+            Symbol::NonTerminal(nonterminal) => self.is_nullable(nonterminal),
+            Symbol::Terminal(_) => false,
+        })
+    }
+
+    /// Returns the FIRST set of `nonterminal`, or [None] if it is not a nonterminal of the
+    /// grammar.
+    pub fn first(&self, nonterminal: &NonTerminal) -> Option<&BTreeSet<Terminal>> {
+        self.first.get(nonterminal)
+    }
+
+    /// Returns the FIRST set of `symbols`, which does not tell whether `symbols` is nullable.
+    pub fn first_of_sequence(&self, symbols: &[Symbol]) -> BTreeSet<Terminal> {
+        first_of_sequence(symbols, &self.first, &self.nullable)
+    }
+
+    /// Returns the FOLLOW set of `nonterminal`, or [None] if it is not a nonterminal of the
+    /// grammar.
+    pub fn follow(&self, nonterminal: &NonTerminal) -> Option<&BTreeSet<Lookahead>> {
+        self.follow.get(nonterminal)
+    }
+}
+
+impl fmt::Display for FirstFollow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[NULLABLE]")?;
+        for nonterminal in self.nonterminals.iter().filter(|&n| self.is_nullable(n)) {
+            writeln!(f, "{}", nonterminal)?;
+        }
+
+        writeln!(f, "\n[FIRST]")?;
+        for nonterminal in self.nonterminals.iter() {
+            writeln!(f, "{}:{}", nonterminal, join_set(&self.first[nonterminal]))?;
+        }
+
+        writeln!(f, "\n[FOLLOW]")?;
+        for nonterminal in self.nonterminals.iter() {
+            writeln!(f, "{}:{}", nonterminal, join_set(&self.follow[nonterminal]))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Grammar {
+    /// Computes the nullable nonterminals, then the FIRST and FOLLOW sets, each by iterating
+    /// until a fixed point is reached. Returns an error if the grammar is not context-free.
+    pub fn first_follow(&self) -> Result<FirstFollow, GrammarError> {
+        if !self.is_context_free() {
+            return Err(GrammarError::new(
+                "FIRST and FOLLOW sets are only defined for context-free grammars",
+                0,
+            ));
+        }
+
+        // The start symbol may have no production, as in grammars of the empty language.
+        let mut nonterminals = self.nonterminals().into_iter().cloned().collect::<Vec<_>>();
+        if !nonterminals.contains(self.start_symbol()) {
+            nonterminals.insert(0, self.start_symbol().clone());
+        }
+
+        let productions = self
+            .productions()
+            .iter()
+            .map(|production| (production.nonterminal().unwrap(), production.rhs.as_slice()))
+            .collect::<Vec<_>>();
+
+        let mut nullable = BTreeSet::<NonTerminal>::new();
+        let mut changed = true;
+
+        while changed {
+            changed = false;
+
+            for &(nonterminal, rhs) in productions.iter() {
+                let is_nullable = rhs.iter().all(|symbol| match symbol {
+                    Symbol::NonTerminal(symbol) => nullable.contains(symbol),
+                    Symbol::Terminal(_) => false,
+                });
+
+                if is_nullable && !nullable.contains(nonterminal) {
+                    nullable.insert(nonterminal.clone());
+                    changed = true;
+                }
+            }
+        }
+
+        let mut first = nonterminals
+            .iter()
+            .map(|nonterminal| (nonterminal.clone(), BTreeSet::new()))
+            .collect::<BTreeMap<_, _>>();
+        changed = true;
+
+        while changed {
+            changed = false;
+
+            for &(nonterminal, rhs) in productions.iter() {
+                let rhs_first = first_of_sequence(rhs, &first, &nullable);
+                let set = first.get_mut(nonterminal).unwrap();
+                let len = set.len();
+
+                set.extend(rhs_first);
+                changed |= set.len() != len;
+            }
+        }
+
+        let mut follow = nonterminals
+            .iter()
+            .map(|nonterminal| (nonterminal.clone(), BTreeSet::new()))
+            .collect::<BTreeMap<_, _>>();
+        follow.get_mut(self.start_symbol()).unwrap().insert(Lookahead::End);
+        changed = true;
+
+        while changed {
+            changed = false;
+
+            for &(nonterminal, rhs) in productions.iter() {
+                for (i, symbol) in rhs.iter().enumerate() {
+                    let symbol = match symbol {
+                        Symbol::NonTerminal(symbol) => symbol,
+                        Symbol::Terminal(_) => continue,
+                    };
+
+                    // Whatever starts the rest of the production follows the symbol, and so does
+                    // whatever follows the nonterminal if the rest can vanish.
+                    let rest = &rhs[(i + 1)..];
+                    let mut lookaheads = first_of_sequence(rest, &first, &nullable)
+                        .into_iter()
+                        .map(Lookahead::Terminal)
+                        .collect::<Vec<_>>();
+
+                    let rest_is_nullable = rest.iter().all(|symbol| match symbol {
+                        Symbol::NonTerminal(symbol) => nullable.contains(symbol),
+                        Symbol::Terminal(_) => false,
+                    });
+
+                    if rest_is_nullable {
+                        lookaheads.extend(follow[nonterminal].iter().cloned());
+                    }
+
+                    let set = follow.entry(symbol.clone()).or_default();
+                    let len = set.len();
+
+                    set.extend(lookaheads);
+                    changed |= set.len() != len;
+                }
+            }
+        }
+
+        Ok(FirstFollow { nonterminals, nullable, first, follow })
+    }
+}
+
+/// Returns the terminals that can start a sequence derived from `symbols`. Nonterminals without
+/// a FIRST set start nothing.
+fn first_of_sequence(
+    symbols: &[Symbol],
+    first: &BTreeMap<NonTerminal, BTreeSet<Terminal>>,
+    nullable: &BTreeSet<NonTerminal>,
+) -> BTreeSet<Terminal> {
+    let mut terminals = BTreeSet::new();
+
+    for symbol in symbols {
+        match symbol {
+            Symbol::Terminal(terminal) => {
+                terminals.insert(terminal.clone());
+                break;
+            }
+            Symbol::NonTerminal(nonterminal) => {
+                terminals.extend(first.get(nonterminal).into_iter().flatten().cloned());
+
+                if !nullable.contains(nonterminal) {
+                    break;
+                }
+            }
+        }
+    }
+
+    terminals
+}
+
+/// Writes each item of a set preceded by a space. Commas cannot separate the items, as `,` is a
+/// terminal too.
+fn join_set<T>(set: &BTreeSet<T>) -> String
+where
+    T: fmt::Display,
+{
+    set.iter().map(|item| format!(" {}", item)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{Production, EXPRESSIONS};
+    use crate::state_machine::{RegularGrammar, StateMachine};
+
+    fn nonterminal(name: &str) -> NonTerminal {
+        NonTerminal(name.to_owned())
+    }
+
+    fn strings<T>(set: &BTreeSet<T>) -> Vec<String>
+    where
+        T: fmt::Display,
+    {
+        set.iter().map(T::to_string).collect()
+    }
+
+    #[test]
+    fn test_expression_grammar() {
+        let grammar = Grammar::parse(EXPRESSIONS).unwrap();
+
+        let sets = grammar.first_follow().unwrap();
+
+        assert!(sets.is_nullable(&nonterminal("e1")));
+        assert!(sets.is_nullable(&nonterminal("t1")));
+        assert!(!sets.is_nullable(&nonterminal("e")));
+
+        assert_eq!(strings(sets.first(&nonterminal("e")).unwrap()), ["(", "id"]);
+        assert_eq!(strings(sets.first(&nonterminal("e1")).unwrap()), ["+"]);
+        assert_eq!(strings(sets.first(&nonterminal("t1")).unwrap()), ["*"]);
+
+        assert_eq!(strings(sets.follow(&nonterminal("e")).unwrap()), [")", "$"]);
+        assert_eq!(strings(sets.follow(&nonterminal("e1")).unwrap()), [")", "$"]);
+        assert_eq!(strings(sets.follow(&nonterminal("t")).unwrap()), [")", "+", "$"]);
+        assert_eq!(strings(sets.follow(&nonterminal("f")).unwrap()), [")", "*", "+", "$"]);
+
+        let unknown = nonterminal("x");
+        assert_eq!(sets.first(&unknown), None);
+        assert_eq!(sets.follow(&unknown), None);
+        assert!(sets.first_of_sequence(&[Symbol::NonTerminal(unknown)]).is_empty());
+    }
+
+    #[test]
+    fn test_nullable_chain() {
+        let grammar = Grammar::parse(
+            "<s> ::= a <b> <d> h\n\
+             <b> ::= c <c>\n\
+             <c> ::= b <c> | (nothing)\n\
+             <d> ::= <e> <f>\n\
+             <e> ::= g | (nothing)\n\
+             <f> ::= f | (nothing)\n",
+        )
+        .unwrap();
+
+        let sets = grammar.first_follow().unwrap();
+        let d = nonterminal("d");
+
+        assert!(sets.is_nullable(&d));
+        assert_eq!(strings(sets.first(&d).unwrap()), ["f", "g"]);
+        assert_eq!(strings(sets.follow(&d).unwrap()), ["h"]);
+        assert_eq!(strings(sets.follow(&nonterminal("c")).unwrap()), ["f", "g", "h"]);
+        assert_eq!(strings(sets.follow(&nonterminal("e")).unwrap()), ["f", "h"]);
+
+        let sequence = [Symbol::NonTerminal(d), Symbol::Terminal(Terminal::Literal("h".into()))];
+        assert_eq!(strings(&sets.first_of_sequence(&sequence)), ["f", "g", "h"]);
+        assert!(!sets.is_nullable_sequence(&sequence));
+    }
+
+    #[test]
+    fn test_syntax() {
+        let grammar = Grammar::parse(include_str!("../../../../specs/syntax.in")).unwrap();
+        let sets = grammar.first_follow().unwrap();
+
+        assert!(sets.is_nullable(&nonterminal("program")));
+        assert_eq!(strings(sets.first(&nonterminal("stmt")).unwrap()), ["if", "while", "<ident>"]);
+        assert_eq!(strings(sets.follow(&nonterminal("stmt_list")).unwrap()), ["}", "$"]);
+        assert_eq!(
+            strings(sets.follow(&nonterminal("expr")).unwrap()),
+            [",", ";", "]", "{", "<op>"]
+        );
+    }
+
+    #[test]
+    fn test_empty_language() {
+        let state_machine = StateMachine::from_regexp("[]").unwrap();
+        let grammar = RegularGrammar::from(&state_machine);
+        assert!(grammar.grammar().productions().is_empty());
+
+        let sets = grammar.grammar().first_follow().unwrap();
+        let start_symbol = grammar.grammar().start_symbol();
+
+        assert!(!sets.is_nullable(start_symbol));
+        assert!(sets.first(start_symbol).unwrap().is_empty());
+        assert_eq!(strings(sets.follow(start_symbol).unwrap()), ["$"]);
+
+        let table = grammar.grammar().ll1_table().unwrap();
+        assert!(table.is_ll1());
+        assert!(table.expected(start_symbol).is_empty());
+    }
+
+    #[test]
+    fn test_undefined_nonterminal() {
+        let (s, a) = (nonterminal("s"), nonterminal("a"));
+        let production = Production {
+            lhs: vec![Symbol::NonTerminal(s.clone())],
+            rhs: vec![
+                Symbol::NonTerminal(a.clone()),
+                Symbol::Terminal(Terminal::Literal("x".into())),
+            ],
+        };
+        let sets = Grammar::new(s, vec![production]).first_follow().unwrap();
+
+        assert_eq!(sets.first(&a), None);
+        assert_eq!(strings(sets.follow(&a).unwrap()), ["x"]);
+    }
+
+    #[test]
+    fn test_not_context_free() {
+        let grammar = Grammar::parse("<s> ::= a <b>\na <b> ::= a\n").unwrap();
+        assert!(grammar.first_follow().is_err());
+    }
+}
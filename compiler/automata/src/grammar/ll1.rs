@@ -0,0 +1,228 @@
+use crate::grammar::{Grammar, GrammarError, Lookahead, NonTerminal, Production};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Cell of an LL(1) table holding more than one production.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Ll1Conflict {
+    /// Nonterminal on top of the stack.
+    pub nonterminal: NonTerminal,
+    /// Next terminal of the input.
+    pub lookahead: Lookahead,
+    /// Indices of the competing productions in [Grammar::productions].
+    pub productions: Vec<usize>,
+}
+
+/// LL(1) parsing table of a context-free grammar, mapping each nonterminal and lookahead to the
+/// productions that may be applied. The grammar is LL(1) if no cell holds more than one
+/// production.
+#[derive(Clone, Debug)]
+pub struct Ll1Table {
+    /// The start symbol of the grammar.
+    start_symbol: NonTerminal,
+    /// All nonterminals, in definition order.
+    nonterminals: Vec<NonTerminal>,
+    /// All productions of the grammar, indexed by the cells.
+    productions: Vec<Production>,
+    /// Productions that may be applied in each non-empty cell.
+    cells: BTreeMap<(NonTerminal, Lookahead), BTreeSet<usize>>,
+}
+
+impl Ll1Table {
+    /// Returns the start symbol of the grammar.
+    pub fn start_symbol(&self) -> &NonTerminal {
+        &self.start_symbol
+    }
+
+    /// Returns all productions of the grammar.
+    pub fn productions(&self) -> &[Production] {
+        &self.productions
+    }
+
+    /// Returns the index of the production to apply when `nonterminal` is expanded with
+    /// `lookahead` as the next terminal. Returns [None] if the cell is empty or conflicting.
+    pub fn production(&self, nonterminal: &NonTerminal, lookahead: &Lookahead) -> Option<usize> {
+        let cell = self.cells.get(&(nonterminal.clone(), lookahead.clone()))?;
+
+        match cell.len() {
+            1 => cell.first().copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the lookaheads with a non-empty cell for `nonterminal`, which are the terminals
+    /// expected when it is expanded.
+    pub fn expected(&self, nonterminal: &NonTerminal) -> Vec<&Lookahead> {
+        self.cells
+            .keys()
+            .filter(|(cell_nonterminal, _)| cell_nonterminal == nonterminal)
+            .map(|(_, lookahead)| lookahead)
+            .collect()
+    }
+
+    /// Returns every cell holding more than one production, ordered by nonterminal definition
+    /// and lookahead.
+    pub fn conflicts(&self) -> Vec<Ll1Conflict> {
+        let mut conflicts = self
+            .cells
+            .iter()
+            .filter(|(_, productions)| productions.len() > 1)
+            .map(|((nonterminal, lookahead), productions)| Ll1Conflict {
+                nonterminal: nonterminal.clone(),
+                lookahead: lookahead.clone(),
+                productions: productions.iter().copied().collect(),
+            })
+            .collect::<Vec<_>>();
+
+        conflicts.sort_by_key(|conflict| {
+            let position = self.nonterminals.iter().position(|n| *n == conflict.nonterminal);
+            (position, conflict.lookahead.clone())
+        });
+
+        conflicts
+    }
+
+    /// Returns whether no cell holds more than one production.
+    pub fn is_ll1(&self) -> bool {
+        self.cells.values().all(|productions| productions.len() <= 1)
+    }
+}
+
+impl fmt::Display for Ll1Table {
+    /// Writes each non-empty cell as `<nonterminal>, lookahead: productions`, using the
+    /// 1-based production numbers of the grammar, followed by the conflicts if there are any.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[TABLE]")?;
+
+        for nonterminal in self.nonterminals.iter() {
+            for ((_, lookahead), productions) in self
+                .cells
+                .iter()
+                .filter(|((cell_nonterminal, _), _)| cell_nonterminal == nonterminal)
+            {
+                let numbers = productions.iter().map(|i| (i + 1).to_string()).collect::<Vec<_>>();
+                writeln!(f, "{}, {}: {}", nonterminal, lookahead, numbers.join(" "))?;
+            }
+        }
+
+        let conflicts = self.conflicts();
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(f, "\n[CONFLICTS]")?;
+
+        for conflict in conflicts {
+            writeln!(f, "{}, {}:", conflict.nonterminal, conflict.lookahead)?;
+
+            for i in conflict.productions {
+                writeln!(f, "    {}: {}", i + 1, self.productions[i])?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Grammar {
+    /// Builds the LL(1) table of the grammar. Production `A ::= α` goes in the cells of `A` for
+    /// each terminal of FIRST(α), and for each lookahead of FOLLOW(A) if α is nullable. Every
+    /// conflicting cell is kept, so [Ll1Table::conflicts] reports all of them at once. Returns an
+    /// error if the grammar is not context-free.
+    pub fn ll1_table(&self) -> Result<Ll1Table, GrammarError> {
+        let sets = self.first_follow()?;
+        let mut cells = BTreeMap::<(NonTerminal, Lookahead), BTreeSet<usize>>::new();
+
+        for (i, production) in self.productions().iter().enumerate() {
+            let nonterminal = production.nonterminal().unwrap();
+
+            let mut lookaheads = sets
+                .first_of_sequence(&production.rhs)
+                .into_iter()
+                .map(Lookahead::Terminal)
+                .collect::<Vec<_>>();
+
+            if sets.is_nullable_sequence(&production.rhs) {
+                lookaheads.extend(sets.follow(nonterminal).into_iter().flatten().cloned());
+            }
+
+            for lookahead in lookaheads {
+                cells.entry((nonterminal.clone(), lookahead)).or_default().insert(i);
+            }
+        }
+
+        Ok(Ll1Table {
+            start_symbol: self.start_symbol().clone(),
+            nonterminals: self.nonterminals().into_iter().cloned().collect(),
+            productions: self.productions().to_vec(),
+            cells,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{Terminal, EXPRESSIONS};
+
+    fn nonterminal(name: &str) -> NonTerminal {
+        NonTerminal(name.to_owned())
+    }
+
+    fn literal(text: &str) -> Lookahead {
+        Lookahead::Terminal(Terminal::Literal(text.to_owned()))
+    }
+
+    #[test]
+    fn test_expression_grammar() {
+        let grammar = Grammar::parse(EXPRESSIONS).unwrap();
+
+        let table = grammar.ll1_table().unwrap();
+        assert!(table.is_ll1());
+        assert!(table.conflicts().is_empty());
+
+        let e1 = nonterminal("e1");
+        assert_eq!(table.production(&e1, &literal("+")), Some(1));
+        assert_eq!(table.production(&e1, &literal(")")), Some(2));
+        assert_eq!(table.production(&e1, &Lookahead::End), Some(2));
+        assert_eq!(table.production(&e1, &literal("id")), None);
+        assert_eq!(table.expected(&nonterminal("f")), [&literal("("), &literal("id")]);
+    }
+
+    #[test]
+    fn test_syntax_conflicts() {
+        let grammar = Grammar::parse(include_str!("../../../../specs/syntax.in")).unwrap();
+        let table = grammar.ll1_table().unwrap();
+        assert!(!table.is_ll1());
+
+        let conflicts = table.conflicts();
+        let conflict = |name: &str, lookahead: Lookahead| {
+            conflicts
+                .iter()
+                .find(|conflict| {
+                    conflict.nonterminal == nonterminal(name) && conflict.lookahead == lookahead
+                })
+                .map(|conflict| conflict.productions.clone())
+        };
+
+        // <expr> ::= <ident> | ... | <expr><op><expr>
+        let ident = Lookahead::Terminal(Terminal::Token("ident".to_owned()));
+        assert_eq!(conflict("expr", ident.clone()), Some(vec![0, 3]));
+
+        // The if and if-else productions both start with `if`.
+        let if_productions =
+            grammar.productions_for(&nonterminal("if")).map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(conflict("if", literal("if")), Some(if_productions));
+
+        assert!(conflict("decl", ident.clone()).is_some());
+        assert!(conflict("stmt_list", literal("while")).is_some());
+        assert_eq!(conflict("stmt_list", literal("}")), None);
+
+        // Every conflict is reported, not only the first one.
+        assert!(conflicts.len() > 5);
+        for conflict in conflicts.iter() {
+            assert!(conflict.productions.len() > 1);
+            assert_eq!(table.production(&conflict.nonterminal, &conflict.lookahead), None);
+        }
+    }
+}
@@ -0,0 +1,288 @@
+/// Nullable nonterminals, FIRST and FOLLOW sets.
+mod first_follow;
+
+/// LL(1) parsing tables and their conflicts.
+mod ll1;
+
+/// Parsing of grammars written in BNF notation.
+mod parse;
+
+pub use self::first_follow::*;
+pub use self::ll1::*;
+
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+/// Expression grammar from the Dragon Book, with left recursion removed. LL(1), with nullable
+/// nonterminals.
+#[cfg(test)]
+pub(crate) const EXPRESSIONS: &str = include_str!("../../../grammars/expressions.in");
+
+/// Nonterminal, written `<name>` in BNF notation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct NonTerminal(pub String);
+
+impl NonTerminal {
+    /// Returns the name of the nonterminal, without the angle brackets.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NonTerminal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{}>", self.0)
+    }
+}
+
+/// Terminal of a grammar.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Terminal {
+    /// Terminal matching the given text, such as `if` or `:=`.
+    Literal(String),
+    /// Terminal standing for a whole category of tokens defined outside of the grammar, such as
+    /// `<ident>` in `syntax.in`. Written like a nonterminal, but never defined by a rule.
+    Token(String),
+}
+
+impl fmt::Display for Terminal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(text) if parse::is_plain_literal(text) => write!(f, "{}", text),
+            Self::Literal(text) => write!(f, "\"{}\"", text),
+            Self::Token(name) => write!(f, "<{}>", name),
+        }
+    }
+}
+
+/// Terminal or nonterminal appearing in a production.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Symbol {
+    /// Terminal symbol.
+    Terminal(Terminal),
+    /// Nonterminal symbol.
+    NonTerminal(NonTerminal),
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Terminal(terminal) => write!(f, "{}", terminal),
+            Self::NonTerminal(nonterminal) => write!(f, "{}", nonterminal),
+        }
+    }
+}
+
+/// Rule replacing the symbols of the left-hand side with those of the right-hand side.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Production {
+    /// The replaced symbols, which contain at least one nonterminal.
+    pub lhs: Vec<Symbol>,
+    /// The replacement. An empty right-hand side stands for the empty sequence.
+    pub rhs: Vec<Symbol>,
+}
+
+impl Production {
+    /// Returns the left-hand side if it is made of a single nonterminal, as in context-free
+    /// grammars.
+    pub fn nonterminal(&self) -> Option<&NonTerminal> {
+        match self.lhs.as_slice() {
+            [Symbol::NonTerminal(nonterminal)] => Some(nonterminal),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the production replaces its left-hand side with the empty sequence.
+    pub fn is_epsilon(&self) -> bool {
+        self.rhs.is_empty()
+    }
+}
+
+impl fmt::Display for Production {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ::= ", join_symbols(&self.lhs))?;
+
+        if self.rhs.is_empty() {
+            write!(f, "(nothing)")
+        } else {
+            write!(f, "{}", join_symbols(&self.rhs))
+        }
+    }
+}
+
+/// Grammar written in the BNF notation of `specs/syntax.in` and `specs/lexic.txt`.
+#[derive(Clone, Debug)]
+pub struct Grammar {
+    /// The start symbol.
+    start_symbol: NonTerminal,
+    /// All productions, in definition order.
+    productions: Vec<Production>,
+}
+
+impl Grammar {
+    /// Creates a grammar from its start symbol and productions, in definition order.
+    pub fn new(start_symbol: NonTerminal, productions: Vec<Production>) -> Self {
+        Self { start_symbol, productions }
+    }
+
+    /// Returns the start symbol.
+    pub fn start_symbol(&self) -> &NonTerminal {
+        &self.start_symbol
+    }
+
+    /// Sets the start symbol, which must appear on the left-hand side of a production.
+    pub fn set_start_symbol(&mut self, name: &str) -> Result<(), GrammarError> {
+        match self.nonterminals().into_iter().find(|nonterminal| nonterminal.name() == name) {
+            Some(nonterminal) => {
+                self.start_symbol = nonterminal.clone();
+                Ok(())
+            }
+            None => Err(GrammarError::new(format!("Undefined start symbol <{}>", name), 0)),
+        }
+    }
+
+    /// Returns all productions, in definition order.
+    pub fn productions(&self) -> &[Production] {
+        &self.productions
+    }
+
+    /// Returns the productions whose left-hand side is exactly `nonterminal`, along with their
+    /// index in [Grammar::productions].
+    pub fn productions_for<'a>(
+        &'a self,
+        nonterminal: &'a NonTerminal,
+    ) -> impl Iterator<Item = (usize, &'a Production)> {
+        self.productions
+            .iter()
+            .enumerate()
+            .filter(move |(_, production)| production.nonterminal() == Some(nonterminal))
+    }
+
+    /// Returns all nonterminals appearing on the left-hand side of a production, in definition
+    /// order.
+    pub fn nonterminals(&self) -> Vec<&NonTerminal> {
+        let mut seen = HashSet::new();
+
+        self.productions
+            .iter()
+            .flat_map(|production| production.lhs.iter())
+            .filter_map(|symbol| match symbol {
+                Symbol::NonTerminal(nonterminal) => Some(nonterminal),
+                Symbol::Terminal(_) => None,
+            })
+            .filter(|&nonterminal| seen.insert(nonterminal))
+            .collect()
+    }
+
+    /// Returns all terminals, in order of first appearance.
+    pub fn terminals(&self) -> Vec<&Terminal> {
+        let mut seen = HashSet::new();
+
+        self.productions
+            .iter()
+            .flat_map(|production| production.lhs.iter().chain(production.rhs.iter()))
+            .filter_map(|symbol| match symbol {
+                Symbol::Terminal(terminal) => Some(terminal),
+                Symbol::NonTerminal(_) => None,
+            })
+            .filter(|&terminal| seen.insert(terminal))
+            .collect()
+    }
+
+    /// Returns whether the left-hand side of every production is a single nonterminal.
+    pub fn is_context_free(&self) -> bool {
+        self.productions.iter().all(|production| production.nonterminal().is_some())
+    }
+
+    /// Returns whether the grammar is context-free and every production is made of terminals,
+    /// optionally followed by a single nonterminal.
+    pub fn is_right_linear(&self) -> bool {
+        self.is_context_free()
+            && self.productions.iter().all(|production| {
+                let rhs = &production.rhs;
+
+                rhs.iter()
+                    .take(rhs.len().saturating_sub(1))
+                    .all(|symbol| matches!(symbol, Symbol::Terminal(_)))
+            })
+    }
+}
+
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut written = Vec::<&[Symbol]>::new();
+
+        for production in self.productions.iter() {
+            let lhs = production.lhs.as_slice();
+
+            if written.contains(&lhs) {
+                continue;
+            }
+
+            if !written.is_empty() {
+                writeln!(f)?;
+            }
+
+            written.push(lhs);
+
+            // Continuation lines are aligned on the `=`, as in the specs.
+            let lhs_string = join_symbols(lhs);
+            let indent = lhs_string.chars().count() + 3;
+
+            for (i, production) in
+                self.productions.iter().filter(|production| production.lhs == lhs).enumerate()
+            {
+                if i == 0 {
+                    write!(f, "{} ::= ", lhs_string)?;
+                } else {
+                    write!(f, "{:indent$}| ", "", indent = indent)?;
+                }
+
+                if production.rhs.is_empty() {
+                    writeln!(f, "(nothing)")?;
+                } else {
+                    writeln!(f, "{}", join_symbols(&production.rhs))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when a grammar cannot be parsed or analyzed.
+#[derive(Clone, Debug)]
+pub struct GrammarError {
+    /// Error message.
+    message: String,
+    /// Line at which the error occurred, or `0` if the error concerns the whole grammar.
+    line: usize,
+}
+
+impl GrammarError {
+    /// Creates a new error.
+    pub fn new<S>(message: S, line: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { message: message.into(), line }
+    }
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "Error on line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+impl Error for GrammarError {}
+
+/// Joins symbols with spaces, so that adjacent terminals are not read back as one.
+fn join_symbols(symbols: &[Symbol]) -> String {
+    symbols.iter().map(Symbol::to_string).collect::<Vec<_>>().join(" ")
+}
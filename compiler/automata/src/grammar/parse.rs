@@ -0,0 +1,401 @@
+use crate::grammar::{Grammar, GrammarError, NonTerminal, Production, Symbol, Terminal};
+use std::collections::HashSet;
+
+/// Characters that are terminals on their own, even when written next to other characters, as
+/// in `[<array_lit_content>,]`.
+const SEPARATORS: [char; 8] = [';', ',', '(', ')', '[', ']', '{', '}'];
+
+impl Grammar {
+    /// Parses a grammar written in BNF notation.
+    ///
+    /// Rules have the shape `<name> ::= ...`. Alternatives are separated by `|` and may continue
+    /// on lines starting with `|`. Nonterminals are written `<name>` and runs of other characters
+    /// are terminals, except for [SEPARATORS], which always stand alone. Terminals may be quoted
+    /// (`"||"`) to include `|` or `<`. `(nothing)` stands for the empty sequence and an
+    /// alternative consisting of `...` stands for all characters between the previous and the
+    /// next alternative, as in `1 | 2 | ... | 9`. Lines starting with `//` are comments.
+    ///
+    /// Nonterminals that are never defined, such as `<ident>` in `syntax.in`, stand for tokens
+    /// and become [Terminal::Token]s. The start symbol is the first nonterminal that no
+    /// production uses, or the first defined nonterminal if all of them are used.
+    pub fn parse(input: &str) -> Result<Self, GrammarError> {
+        let mut productions = Vec::<Production>::new();
+        let mut lhs = None::<Vec<Symbol>>;
+
+        for (i, line) in input.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+
+            let alternatives = if line.is_empty() || line.starts_with("//") {
+                continue;
+            } else if let Some(alternatives) = line.strip_prefix('|') {
+                if lhs.is_none() {
+                    return Err(GrammarError::new("Alternative without a rule", line_number));
+                }
+
+                alternatives
+            } else {
+                let (left, alternatives) = line.split_once("::=").ok_or_else(|| {
+                    GrammarError::new("Expected a rule of the shape <name> ::= ...", line_number)
+                })?;
+
+                let left = parse_symbols(left);
+                if !left.iter().any(|symbol| matches!(symbol, Symbol::NonTerminal(_))) {
+                    return Err(GrammarError::new(
+                        "The left-hand side of a rule must contain a nonterminal",
+                        line_number,
+                    ));
+                }
+
+                lhs = Some(left);
+
+                // The first alternative may start on the next line.
+                if alternatives.trim().is_empty() {
+                    continue;
+                }
+
+                alternatives
+            };
+
+            let lhs = lhs.clone().unwrap();
+            let mut previous_char = None::<char>;
+            let mut pending_range = false;
+
+            for alternative in split_alternatives(alternatives) {
+                let alternative = alternative.trim();
+
+                if alternative == "..." {
+                    previous_char.ok_or_else(|| {
+                        GrammarError::new("Range without a starting character", line_number)
+                    })?;
+
+                    pending_range = true;
+                    continue;
+                }
+
+                let rhs = if alternative == "(nothing)" {
+                    Vec::new()
+                } else {
+                    let rhs = parse_symbols(alternative);
+
+                    if rhs.is_empty() {
+                        return Err(GrammarError::new("Empty alternative", line_number));
+                    }
+
+                    rhs
+                };
+
+                let single_char = match rhs.as_slice() {
+                    [Symbol::Terminal(Terminal::Literal(text))] if text.chars().count() == 1 => {
+                        text.chars().next()
+                    }
+                    _ => None,
+                };
+
+                if pending_range {
+                    let (first, last) = match (previous_char, single_char) {
+                        (Some(first), Some(last)) if first < last => (first, last),
+                        _ => {
+                            return Err(GrammarError::new(
+                                "Ranges must be between two increasing characters",
+                                line_number,
+                            ))
+                        }
+                    };
+
+                    for c in char::from_u32(u32::from(first) + 1).into_iter().flat_map(|c| c..last)
+                    {
+                        productions.push(Production {
+                            lhs: lhs.clone(),
+                            rhs: vec![Symbol::Terminal(Terminal::Literal(c.to_string()))],
+                        });
+                    }
+
+                    pending_range = false;
+                }
+
+                previous_char = single_char;
+                productions.push(Production { lhs: lhs.clone(), rhs });
+            }
+
+            if pending_range {
+                return Err(GrammarError::new("Range without an ending character", line_number));
+            }
+        }
+
+        let defined = productions
+            .iter()
+            .flat_map(|production| production.lhs.iter())
+            .filter_map(|symbol| match symbol {
+                Symbol::NonTerminal(nonterminal) => Some(nonterminal.clone()),
+                Symbol::Terminal(_) => None,
+            })
+            .collect::<HashSet<_>>();
+
+        for production in productions.iter_mut() {
+            for symbol in production.rhs.iter_mut() {
+                if let Symbol::NonTerminal(nonterminal) = symbol {
+                    if !defined.contains(nonterminal) {
+                        *symbol = Symbol::Terminal(Terminal::Token(nonterminal.0.clone()));
+                    }
+                }
+            }
+        }
+
+        let used =
+            productions.iter().flat_map(|production| production.rhs.iter()).collect::<HashSet<_>>();
+
+        let mut candidates = productions.iter().filter_map(Production::nonterminal);
+        let start_symbol = candidates
+            .clone()
+            .find(|&nonterminal| !used.contains(&Symbol::NonTerminal(nonterminal.clone())))
+            .or_else(|| candidates.next())
+            .cloned()
+            .ok_or_else(|| GrammarError::new("No rule defines a single nonterminal", 0))?;
+
+        Ok(Self { start_symbol, productions })
+    }
+}
+
+/// Returns whether `text` reads back as a single literal terminal when written without quotes.
+pub(super) fn is_plain_literal(text: &str) -> bool {
+    !text.is_empty()
+        && text != "..."
+        && text != "(nothing)"
+        && parse_symbols(text) == [Symbol::Terminal(Terminal::Literal(text.to_owned()))]
+        && !text.contains('|')
+}
+
+/// Splits the right-hand side of a rule on the `|` characters outside of quoted terminals.
+fn split_alternatives(input: &str) -> Vec<&str> {
+    let mut alternatives = Vec::new();
+    let mut start = 0;
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                if let Some(len) = quoted_len(&input[i..]) {
+                    while chars.peek().is_some_and(|&(j, _)| j < i + len) {
+                        chars.next();
+                    }
+                }
+            }
+            '|' => {
+                alternatives.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+
+    alternatives.push(&input[start..]);
+    alternatives
+}
+
+/// Splits an alternative or a left-hand side into its symbols. Nonterminals are not resolved
+/// into tokens yet.
+fn parse_symbols(input: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut literal = String::new();
+    let mut chars = input.char_indices().peekable();
+
+    let flush = |literal: &mut String, symbols: &mut Vec<Symbol>| {
+        if !literal.is_empty() {
+            symbols.push(Symbol::Terminal(Terminal::Literal(std::mem::take(literal))));
+        }
+    };
+
+    while let Some((i, c)) = chars.next() {
+        let rest = &input[i..];
+
+        let (symbol, len) = if let Some(len) = nonterminal_len(rest).filter(|_| c == '<') {
+            (Symbol::NonTerminal(NonTerminal(rest[1..(len - 1)].to_owned())), len)
+        } else if let Some(len) = quoted_len(rest).filter(|_| c == '"') {
+            (Symbol::Terminal(Terminal::Literal(rest[1..(len - 1)].to_owned())), len)
+        } else if SEPARATORS.contains(&c) {
+            (Symbol::Terminal(Terminal::Literal(c.to_string())), 1)
+        } else {
+            if c.is_whitespace() {
+                flush(&mut literal, &mut symbols);
+            } else {
+                literal.push(c);
+            }
+
+            continue;
+        };
+
+        flush(&mut literal, &mut symbols);
+        symbols.push(symbol);
+
+        // Skip the remaining characters of the symbol.
+        while chars.peek().is_some_and(|&(j, _)| j < i + len) {
+            chars.next();
+        }
+    }
+
+    flush(&mut literal, &mut symbols);
+    symbols
+}
+
+/// Returns the length in bytes of the nonterminal at the start of `input`, written `<name>`
+/// with a name made of letters, digits and underscores.
+fn nonterminal_len(input: &str) -> Option<usize> {
+    let name_len = input
+        .strip_prefix('<')?
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|&len| len > 0)?;
+
+    input[(name_len + 1)..].starts_with('>').then_some(name_len + 2)
+}
+
+/// Returns the length in bytes of the quoted terminal at the start of `input`. The quotes must
+/// enclose at least one character, none of which may be a quote, a `<` or whitespace.
+fn quoted_len(input: &str) -> Option<usize> {
+    let content_len = input
+        .strip_prefix('"')?
+        .find(|c: char| c == '"' || c == '<' || c.is_whitespace())
+        .filter(|&len| len > 0)?;
+
+    input[(content_len + 1)..].starts_with('"').then_some(content_len + 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn literal(text: &str) -> Terminal {
+        Terminal::Literal(text.to_owned())
+    }
+
+    fn nonterminal(name: &str) -> NonTerminal {
+        NonTerminal(name.to_owned())
+    }
+
+    #[test]
+    fn test_parse_symbols() {
+        let symbols = parse_symbols("[<array_lit_content>,] := '<letter>' \"||\" \"\" <= a<b");
+
+        assert_eq!(
+            symbols.iter().map(Symbol::to_string).collect::<Vec<_>>(),
+            [
+                "[",
+                "<array_lit_content>",
+                ",",
+                "]",
+                ":=",
+                "'",
+                "<letter>",
+                "'",
+                "\"||\"",
+                "\"\"",
+                "<=",
+                "a<b"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_syntax() {
+        let grammar = Grammar::parse(include_str!("../../../../specs/syntax.in")).unwrap();
+
+        assert_eq!(grammar.start_symbol(), &nonterminal("program"));
+        assert!(grammar.is_context_free());
+        assert!(!grammar.is_right_linear());
+
+        let nonterminals = grammar.nonterminals();
+        assert_eq!(nonterminals.len(), 14);
+        assert_eq!(nonterminals[0], &nonterminal("expr"));
+
+        let terminals = grammar.terminals();
+        for terminal in [literal("if"), literal(":="), literal("{"), literal(",")] {
+            assert!(terminals.contains(&&terminal), "{}", terminal);
+        }
+
+        for token in ["ident", "primitive_lit", "op", "int_lit", "primitive"] {
+            assert!(terminals.contains(&&Terminal::Token(token.to_owned())), "{}", token);
+        }
+
+        let if_productions = grammar
+            .productions_for(&nonterminal("if"))
+            .map(|(_, p)| p.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            if_productions,
+            [
+                "<if> ::= if <expr> { <stmt_list> }",
+                "<if> ::= if <expr> { <stmt_list> } else { <stmt_list> }"
+            ]
+        );
+
+        let stmt_list = nonterminal("stmt_list");
+        let stmt_list = grammar.productions_for(&stmt_list).collect::<Vec<_>>();
+        assert!(stmt_list[0].1.is_epsilon());
+
+        // Written grammars read back the same.
+        let reparsed = Grammar::parse(&grammar.to_string()).unwrap();
+        assert_eq!(reparsed.productions(), grammar.productions());
+        assert_eq!(reparsed.start_symbol(), grammar.start_symbol());
+    }
+
+    #[test]
+    fn test_lexic() {
+        let grammar = Grammar::parse(include_str!("../../../../specs/lexic.txt")).unwrap();
+
+        assert_eq!(grammar.start_symbol(), &nonterminal("ident"));
+        assert_eq!(grammar.productions_for(&nonterminal("letter")).count(), 52);
+        assert_eq!(grammar.productions_for(&nonterminal("non_zero_digit")).count(), 9);
+
+        let logical_ops = grammar
+            .productions_for(&nonterminal("logical_op"))
+            .map(|(_, production)| production.rhs.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            logical_ops,
+            [
+                vec![Symbol::Terminal(literal("&&"))],
+                vec![Symbol::Terminal(literal("||"))],
+                vec![Symbol::Terminal(literal("!"))]
+            ]
+        );
+
+        let terminals = grammar.terminals();
+        assert!(terminals.contains(&&Terminal::Token("space".to_owned())));
+        assert!(terminals.contains(&&literal("|")));
+        assert!(terminals.contains(&&literal("\"\"")));
+
+        let reparsed = Grammar::parse(&grammar.to_string()).unwrap();
+        assert_eq!(reparsed.productions(), grammar.productions());
+    }
+
+    #[test]
+    fn test_grammar_classes() {
+        let right_linear =
+            Grammar::parse("<s> ::= a <s> | b c <t>\n<t> ::= (nothing) | c\n").unwrap();
+        assert!(right_linear.is_context_free());
+        assert!(right_linear.is_right_linear());
+
+        let context_free = Grammar::parse("<s> ::= a <s> b | (nothing)\n").unwrap();
+        assert!(context_free.is_context_free());
+        assert!(!context_free.is_right_linear());
+
+        let context_sensitive = Grammar::parse("<s> ::= a <b> c\na <b> ::= a a <b> | a\n").unwrap();
+        assert!(!context_sensitive.is_context_free());
+        assert_eq!(context_sensitive.nonterminals(), [&nonterminal("s"), &nonterminal("b")]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Grammar::parse("| a\n").is_err());
+        assert!(Grammar::parse("<s> a\n").is_err());
+        assert!(Grammar::parse("a ::= b\n").is_err());
+        assert!(Grammar::parse("<s> ::= a |\n").is_err());
+        assert!(Grammar::parse("<s> ::= ... | b\n").is_err());
+        assert!(Grammar::parse("// Only a comment\n").is_err());
+
+        let mut grammar = Grammar::parse("<s> ::= <t>\n<t> ::= a\n").unwrap();
+        grammar.set_start_symbol("t").unwrap();
+        assert_eq!(grammar.start_symbol(), &nonterminal("t"));
+        assert!(grammar.set_start_symbol("u").is_err());
+    }
+}
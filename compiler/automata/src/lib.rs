@@ -0,0 +1,10 @@
+#![allow(dead_code)]
+
+/// Grammars in BNF notation, such as those of the language specification.
+pub mod grammar;
+
+/// Various utility functions.
+pub mod utils;
+
+/// State machine implementation.
+pub mod state_machine;
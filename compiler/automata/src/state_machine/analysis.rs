@@ -0,0 +1,292 @@
+use crate::state_machine::StateMachine;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// Size of the language accepted by a state machine.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LanguageSize {
+    /// No sequence is accepted.
+    Empty,
+    /// A finite number of sequences are accepted.
+    Finite,
+    /// An infinite number of sequences are accepted.
+    Infinite,
+}
+
+/// Results of analyzing a state machine, with all states and symbols sorted.
+#[derive(Clone, Debug)]
+pub struct AnalysisReport {
+    /// States that cannot be reached from the initial state.
+    pub unreachable_states: Vec<String>,
+    /// States from which no final state can be reached.
+    pub dead_states: Vec<String>,
+    /// Pairs of `(state, symbol)` for which no transition exists.
+    pub missing_transitions: Vec<(String, String)>,
+    /// Size of the accepted language.
+    pub language_size: LanguageSize,
+    /// Shortest accepted sequence, if any. Ties are broken alphabetically.
+    pub shortest_accepted: Option<Vec<String>>,
+}
+
+impl AnalysisReport {
+    /// Returns whether the transition function is defined for every state and symbol.
+    pub fn is_complete(&self) -> bool {
+        self.missing_transitions.is_empty()
+    }
+}
+
+impl fmt::Display for AnalysisReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[UNREACHABLE STATES]")?;
+        for state in self.unreachable_states.iter() {
+            writeln!(f, "{}", state)?;
+        }
+
+        writeln!(f, "\n[DEAD STATES]")?;
+        for state in self.dead_states.iter() {
+            writeln!(f, "{}", state)?;
+        }
+
+        writeln!(f, "\n[MISSING TRANSITIONS]")?;
+        for (state, symbol) in self.missing_transitions.iter() {
+            writeln!(f, "{} ---({})--> ?", state, symbol)?;
+        }
+
+        writeln!(f, "\n[LANGUAGE]")?;
+        match self.language_size {
+            LanguageSize::Empty => writeln!(f, "empty")?,
+            LanguageSize::Finite => writeln!(f, "finite")?,
+            LanguageSize::Infinite => writeln!(f, "infinite")?,
+        }
+
+        writeln!(f, "\n[SHORTEST ACCEPTED SEQUENCE]")?;
+        match self.shortest_accepted.as_ref() {
+            Some(sequence) if sequence.is_empty() => write!(f, "(empty sequence)"),
+            Some(sequence) => write!(f, "{}", sequence.concat()),
+            None => write!(f, "(none)"),
+        }
+    }
+}
+
+impl StateMachine {
+    /// Analyzes the state machine for unreachable and dead states, missing transitions and the
+    /// size of the accepted language.
+    pub fn analyze(&self) -> AnalysisReport {
+        AnalysisReport {
+            unreachable_states: self.unreachable_states(),
+            dead_states: self.dead_states(),
+            missing_transitions: self.missing_transitions(),
+            language_size: self.language_size(),
+            shortest_accepted: self.shortest_accepted(),
+        }
+    }
+
+    /// Returns all states that can be reached from the initial state.
+    pub fn reachable_states(&self) -> HashSet<&str> {
+        let mut reachable = HashSet::<&str>::new();
+        let mut stack = vec![self.initial_state.as_str()];
+
+        while let Some(state) = stack.pop() {
+            if reachable.insert(state) {
+                stack.extend(self.successors(state));
+            }
+        }
+
+        reachable
+    }
+
+    /// Returns all states from which a final state can be reached.
+    pub fn productive_states(&self) -> HashSet<&str> {
+        let mut predecessors = HashMap::<&str, Vec<&str>>::new();
+        for (src_state, _, dst_state) in self.iter_transitions() {
+            predecessors.entry(dst_state).or_default().push(src_state);
+        }
+
+        let mut productive = HashSet::<&str>::new();
+        let mut stack = self.final_states().collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if productive.insert(state) {
+                stack.extend(predecessors.get(state).into_iter().flatten().copied());
+            }
+        }
+
+        productive
+    }
+
+    /// Returns the sorted list of states that cannot be reached from the initial state.
+    pub fn unreachable_states(&self) -> Vec<String> {
+        let reachable = self.reachable_states();
+        self.sorted_states()
+            .into_iter()
+            .filter(|state| !reachable.contains(state))
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Returns the sorted list of states from which no final state can be reached.
+    pub fn dead_states(&self) -> Vec<String> {
+        let productive = self.productive_states();
+        self.sorted_states()
+            .into_iter()
+            .filter(|state| !productive.contains(state))
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Returns the sorted list of `(state, symbol)` pairs for which no transition exists.
+    pub fn missing_transitions(&self) -> Vec<(String, String)> {
+        let symbols = self.sorted_symbols();
+
+        self.sorted_states()
+            .into_iter()
+            .flat_map(|state| symbols.iter().map(move |&symbol| (state, symbol)))
+            .filter(|&(state, symbol)| self.next_state(state, symbol).is_none())
+            .map(|(state, symbol)| (state.to_owned(), symbol.to_owned()))
+            .collect()
+    }
+
+    /// Returns whether the transition function is defined for every state and symbol.
+    pub fn is_complete(&self) -> bool {
+        self.states.iter().all(|state| {
+            self.alphabet.iter().all(|symbol| self.next_state(state, symbol).is_some())
+        })
+    }
+
+    /// Returns whether the state machine accepts no sequence.
+    pub fn is_empty(&self) -> bool {
+        !self.productive_states().contains(self.initial_state.as_str())
+    }
+
+    /// Returns the size of the accepted language. The language is infinite if and only if a
+    /// cycle passes through a state that is both reachable and productive.
+    pub fn language_size(&self) -> LanguageSize {
+        let reachable = self.reachable_states();
+        let productive = self.productive_states();
+
+        if !productive.contains(self.initial_state.as_str()) {
+            return LanguageSize::Empty;
+        }
+
+        let useful = reachable.intersection(&productive).copied().collect::<HashSet<_>>();
+
+        /// Depth-first search state of a node.
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Mark {
+            InProgress,
+            Done,
+        }
+
+        let mut marks = HashMap::<&str, Mark>::new();
+
+        for &root in useful.iter() {
+            if marks.contains_key(root) {
+                continue;
+            }
+
+            // Stack of states and their remaining successors.
+            let mut stack = vec![(root, self.successors(root).collect::<Vec<_>>())];
+            marks.insert(root, Mark::InProgress);
+
+            while let Some((state, successors)) = stack.last_mut() {
+                match successors.pop() {
+                    Some(next) if useful.contains(next) => match marks.get(next) {
+                        Some(Mark::InProgress) => return LanguageSize::Infinite,
+                        Some(Mark::Done) => (),
+                        None => {
+                            marks.insert(next, Mark::InProgress);
+                            stack.push((next, self.successors(next).collect()));
+                        }
+                    },
+                    Some(_) => (),
+                    None => {
+                        marks.insert(state, Mark::Done);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+
+        LanguageSize::Finite
+    }
+
+    /// Returns the shortest accepted sequence, breaking ties alphabetically, or [None] if the
+    /// language is empty.
+    pub fn shortest_accepted(&self) -> Option<Vec<String>> {
+        let symbols = self.sorted_symbols();
+        let initial_state = self.initial_state.as_str();
+
+        // Maps each visited state to the state and symbol it was first reached from.
+        let mut parents = HashMap::<&str, Option<(&str, &str)>>::new();
+        let mut queue = VecDeque::from([initial_state]);
+        parents.insert(initial_state, None);
+
+        while let Some(state) = queue.pop_front() {
+            if self.final_states.contains(state) {
+                let mut sequence = Vec::new();
+                let mut current = state;
+
+                while let Some(&Some((parent, symbol))) = parents.get(current) {
+                    sequence.push(symbol.to_owned());
+                    current = parent;
+                }
+
+                sequence.reverse();
+                return Some(sequence);
+            }
+
+            for &symbol in symbols.iter() {
+                if let Some(next_state) = self.next_state(state, symbol) {
+                    if !parents.contains_key(next_state) {
+                        parents.insert(next_state, Some((state, symbol)));
+                        queue.push_back(next_state);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns an iterator over the destination states of all transitions leaving `state`.
+    fn successors<'a>(&'a self, state: &str) -> impl Iterator<Item = &'a str> {
+        self.transitions
+            .get(state)
+            .into_iter()
+            .flat_map(|transitions| transitions.values().map(|state| state.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_analyze_identifier() {
+        let report = fixtures::identifier().analyze();
+
+        assert!(report.unreachable_states.is_empty());
+        assert!(report.dead_states.is_empty());
+        assert_eq!(report.missing_transitions, vec![("initial".to_owned(), "1".to_owned())],);
+        assert!(!report.is_complete());
+        assert_eq!(report.language_size, LanguageSize::Infinite);
+        assert_eq!(report.shortest_accepted, Some(vec!["a".to_owned()]));
+    }
+
+    #[test]
+    fn test_analyze_finite_and_empty() {
+        let finite =
+            StateMachine::from_fa_str("a b c d\n0 1\na\nc\na 0 b\nb 1 c\na 1 c\nd 0 d\n").unwrap();
+
+        assert_eq!(finite.unreachable_states(), vec!["d".to_owned()]);
+        assert_eq!(finite.dead_states(), vec!["d".to_owned()]);
+        assert_eq!(finite.language_size(), LanguageSize::Finite);
+        assert_eq!(finite.shortest_accepted(), Some(vec!["1".to_owned()]));
+
+        let empty = StateMachine::from_fa_str("a b\n0\na\nb\na 0 a\n").unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.language_size(), LanguageSize::Empty);
+        assert_eq!(empty.shortest_accepted(), None);
+    }
+}
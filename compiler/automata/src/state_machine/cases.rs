@@ -0,0 +1,179 @@
+use crate::state_machine::StateMachine;
+use std::error::Error;
+use std::fmt;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Word along with whether a state machine is expected to accept it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TestCase {
+    /// The word, split into grapheme clusters when running the case.
+    pub word: String,
+    /// Whether the word should be accepted.
+    pub should_accept: bool,
+    /// Line of the case in the cases file.
+    pub line: usize,
+}
+
+/// Parses a cases file. Each line holds `accept` or `reject`, followed by the word. Lines
+/// without a word stand for the empty sequence. Empty lines and lines starting with `//` are
+/// ignored.
+pub fn parse_test_cases(input: &str) -> Result<Vec<TestCase>, TestCaseError> {
+    let mut cases = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+
+        let should_accept = match words.next() {
+            Some("accept") => true,
+            Some("reject") => false,
+            _ => {
+                return Err(TestCaseError::new(
+                    "Expected \"accept\" or \"reject\" at the start of the line",
+                    line_number,
+                ))
+            }
+        };
+
+        let word = words.next().unwrap_or_default().to_owned();
+
+        if words.next().is_some() {
+            return Err(TestCaseError::new("Expected a single word", line_number));
+        }
+
+        cases.push(TestCase { word, should_accept, line: line_number });
+    }
+
+    Ok(cases)
+}
+
+/// Results of running test cases against a state machine.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TestReport {
+    /// Each case, along with whether it passed.
+    pub results: Vec<(TestCase, bool)>,
+}
+
+impl TestReport {
+    /// Returns the number of passed cases.
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|(_, passed)| *passed).count()
+    }
+
+    /// Returns the number of failed cases.
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    /// Returns whether all cases passed.
+    pub fn is_success(&self) -> bool {
+        self.failed() == 0
+    }
+}
+
+impl fmt::Display for TestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (case, passed) in self.results.iter() {
+            let status = if *passed { "PASS" } else { "FAIL" };
+            let expectation = if case.should_accept { "accept" } else { "reject" };
+
+            write!(f, "{} line {}: {} \"{}\"", status, case.line, expectation, case.word)?;
+
+            if !passed {
+                let actual = if case.should_accept { "rejected" } else { "accepted" };
+                write!(f, " ({})", actual)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        write!(f, "{} passed, {} failed", self.passed(), self.failed())
+    }
+}
+
+impl StateMachine {
+    /// Runs each case and reports whether the state machine accepts exactly the words it should.
+    pub fn run_test_cases(&self, cases: &[TestCase]) -> TestReport {
+        let results = cases
+            .iter()
+            .map(|case| {
+                let sequence = case.word.graphemes(true).collect::<Vec<_>>();
+                let passed = self.is_accepted(&sequence) == case.should_accept;
+                (case.clone(), passed)
+            })
+            .collect();
+
+        TestReport { results }
+    }
+}
+
+/// Error returned when a cases file cannot be parsed.
+#[derive(Clone, Debug)]
+pub struct TestCaseError {
+    /// Error message.
+    message: String,
+    /// Line at which the error occurred.
+    line: usize,
+}
+
+impl TestCaseError {
+    /// Creates a new error.
+    pub fn new<S>(message: S, line: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { message: message.into(), line }
+    }
+}
+
+impl fmt::Display for TestCaseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error on line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for TestCaseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_case_files() {
+        let machines = [
+            (fixtures::number(), include_str!("../../../state-machines/tests/number.txt")),
+            (fixtures::identifier(), include_str!("../../../state-machines/tests/identifier.txt")),
+        ];
+
+        for (state_machine, cases) in machines {
+            let report = state_machine.run_test_cases(&parse_test_cases(cases).unwrap());
+            assert!(report.is_success(), "{}", report);
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let state_machine = StateMachine::from_regexp("ab*").unwrap();
+        let cases = parse_test_cases("// Comment\naccept abb\n\nreject\naccept b\n").unwrap();
+
+        let report = state_machine.run_test_cases(&cases);
+        assert!(!report.is_success());
+        assert_eq!(
+            report.to_string(),
+            "PASS line 2: accept \"abb\"\n\
+             PASS line 4: reject \"\"\n\
+             FAIL line 5: accept \"b\" (rejected)\n\
+             2 passed, 1 failed"
+        );
+
+        assert!(parse_test_cases("maybe a\n").is_err());
+        assert!(parse_test_cases("accept a b\n").is_err());
+    }
+}
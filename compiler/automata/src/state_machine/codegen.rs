@@ -0,0 +1,169 @@
+use crate::state_machine::StateMachine;
+use std::fmt::Write;
+
+impl StateMachine {
+    /// Returns the source code of a standalone Rust module running the state machine.
+    ///
+    /// The module uses the dense table form of [StateMachine::compile] and exposes the
+    /// `INITIAL_STATE` and `DEAD_STATE` ids, a `step` function moving from one state to the next
+    /// on a symbol, `is_final` and `accepts`, which checks a whole string. Like
+    /// [StateMachine::is_accepted], `accepts` splits the input into grapheme clusters, so the
+    /// module depends on the `unicode-segmentation` crate.
+    pub fn to_rust_module(&self) -> String {
+        let compiled = self.compile();
+        let state_count = compiled.dead_state() as usize;
+        let symbol_count = compiled.symbol_count();
+
+        let state_names = (0..state_count as u32)
+            .map(|state| format!("{:?}", compiled.state_name(state).unwrap()))
+            .collect::<Vec<_>>();
+        let final_states = (0..=state_count as u32)
+            .map(|state| compiled.is_final(state).to_string())
+            .collect::<Vec<_>>();
+
+        let mut output = String::new();
+        writeln!(&mut output, "// Generated by `StateMachine::to_rust_module`. Do not edit.")
+            .unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Id of the initial state.").unwrap();
+        writeln!(&mut output, "pub const INITIAL_STATE: u32 = {};", compiled.initial_state())
+            .unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Id of the dead state, reached on missing transitions.").unwrap();
+        writeln!(&mut output, "pub const DEAD_STATE: u32 = {};", compiled.dead_state()).unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Names of the states, indexed by id.").unwrap();
+        writeln!(
+            &mut output,
+            "pub const STATE_NAMES: [&str; {}] = [{}];",
+            state_count,
+            state_names.join(", ")
+        )
+        .unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Whether each state is final, indexed by id.").unwrap();
+        writeln!(
+            &mut output,
+            "const FINAL_STATES: [bool; {}] = [{}];",
+            state_count + 1,
+            final_states.join(", ")
+        )
+        .unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Number of symbols in the alphabet.").unwrap();
+        writeln!(&mut output, "const SYMBOL_COUNT: usize = {};", symbol_count).unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(
+            &mut output,
+            "/// Next state of each state on each symbol, at index `state * SYMBOL_COUNT + symbol`."
+        )
+        .unwrap();
+        writeln!(&mut output, "const TRANSITIONS: [u32; {}] = [", (state_count + 1) * symbol_count)
+            .unwrap();
+
+        for state in 0..=state_count as u32 {
+            let row = (0..symbol_count as u32)
+                .map(|symbol| compiled.step(state, symbol).to_string())
+                .collect::<Vec<_>>();
+            let name = compiled.state_name(state).unwrap_or("dead state");
+
+            if !row.is_empty() {
+                writeln!(&mut output, "    {}, // {}", row.join(", "), name).unwrap();
+            }
+        }
+
+        writeln!(&mut output, "];").unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Returns the id of `symbol`, if it is part of the alphabet.")
+            .unwrap();
+        writeln!(&mut output, "pub fn symbol_id(symbol: &str) -> Option<u32> {{").unwrap();
+        writeln!(&mut output, "    match symbol {{").unwrap();
+
+        for symbol in 0..symbol_count as u32 {
+            let name = compiled.symbol(symbol).unwrap();
+            writeln!(&mut output, "        {:?} => Some({}),", name, symbol).unwrap();
+        }
+
+        writeln!(&mut output, "        _ => None,").unwrap();
+        writeln!(&mut output, "    }}").unwrap();
+        writeln!(&mut output, "}}").unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Returns the state reached from `state` on `symbol`.").unwrap();
+        writeln!(&mut output, "pub fn step(state: u32, symbol: &str) -> u32 {{").unwrap();
+        writeln!(&mut output, "    match symbol_id(symbol) {{").unwrap();
+        writeln!(
+            &mut output,
+            "        Some(symbol) => TRANSITIONS[state as usize * SYMBOL_COUNT + symbol as usize],"
+        )
+        .unwrap();
+        writeln!(&mut output, "        None => DEAD_STATE,").unwrap();
+        writeln!(&mut output, "    }}").unwrap();
+        writeln!(&mut output, "}}").unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "/// Returns whether `state` is final.").unwrap();
+        writeln!(&mut output, "pub fn is_final(state: u32) -> bool {{").unwrap();
+        writeln!(&mut output, "    FINAL_STATES[state as usize]").unwrap();
+        writeln!(&mut output, "}}").unwrap();
+        writeln!(&mut output).unwrap();
+
+        writeln!(
+            &mut output,
+            "/// Checks if the grapheme clusters of `input` form an accepted sequence."
+        )
+        .unwrap();
+        writeln!(&mut output, "pub fn accepts(input: &str) -> bool {{").unwrap();
+        writeln!(&mut output, "    let mut state = INITIAL_STATE;").unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(
+            &mut output,
+            "    for symbol in unicode_segmentation::UnicodeSegmentation::graphemes(input, true) {{"
+        )
+        .unwrap();
+        writeln!(&mut output, "        state = step(state, symbol);").unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "        if state == DEAD_STATE {{").unwrap();
+        writeln!(&mut output, "            return false;").unwrap();
+        writeln!(&mut output, "        }}").unwrap();
+        writeln!(&mut output, "    }}").unwrap();
+        writeln!(&mut output).unwrap();
+        writeln!(&mut output, "    is_final(state)").unwrap();
+        writeln!(&mut output, "}}").unwrap();
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+    use crate::utils;
+
+    #[test]
+    fn test_to_rust_module() {
+        // The scanner's tests compile the modules generated by its build script and check them
+        // against the state machines they come from.
+        let number = fixtures::number();
+        let module = number.to_rust_module();
+
+        assert!(module.contains("pub const STATE_NAMES: [&str; 2] = [\"initial\", \"final\"];\n"));
+        assert!(module.contains("const FINAL_STATES: [bool; 3] = [false, true, false];\n"));
+        assert!(module.contains(
+            "const TRANSITIONS: [u32; 9] = [\n    \
+             2, 1, 1, // initial\n    \
+             1, 1, 1, // final\n    \
+             2, 2, 2, // dead state\n];\n"
+        ));
+        assert!(module.contains("        \"2\" => Some(2),\n"));
+        assert!(module.contains("UnicodeSegmentation::graphemes(input, true)"));
+
+        // A combining mark forms a single grapheme cluster with the character before it, so
+        // neither the state machine nor its module accepts `a` followed by U+0301, even though
+        // both characters are symbols.
+        let combining =
+            StateMachine::from_fa_str("p q r\na \u{301}\np\nr\np a q\nq \u{301} r\n").unwrap();
+        assert!(!combining.is_accepted(&utils::str_to_grapheme_clusters("a\u{301}")));
+        assert!(combining.is_accepted(&["a", "\u{301}"]));
+        assert!(combining.to_rust_module().contains("UnicodeSegmentation::graphemes(input, true)"));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::state_machine::StateMachine;
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Deterministic state machine with dense state and symbol ids and a flat transition table.
+///
+/// States are numbered from `0`, starting with the initial state, followed by an extra dead
+/// state that every missing transition leads to. Symbols are numbered in sorted order.
+/// Single-byte symbols are looked up in a byte table, all other symbols in a hash map.
+#[derive(Clone, Debug)]
+pub struct CompiledStateMachine {
+    /// Names of the states, indexed by id. The dead state has no name.
+    state_names: Vec<String>,
+    /// Symbols, indexed by id.
+    symbols: Vec<String>,
+    /// Symbol ids of single-byte symbols, indexed by byte, or [NO_SYMBOL].
+    byte_classes: Box<[u32; 256]>,
+    /// Symbol ids of symbols longer than a single byte.
+    other_classes: HashMap<String, u32>,
+    /// Next state of each state on each symbol, at index `state * symbol_count + symbol`.
+    transitions: Vec<u32>,
+    /// Whether each state is final, indexed by id.
+    accepting: Vec<bool>,
+}
+
+/// Marks bytes that are not symbols in the byte table.
+const NO_SYMBOL: u32 = u32::MAX;
+
+impl CompiledStateMachine {
+    /// Returns the id of the initial state.
+    #[inline]
+    pub fn initial_state(&self) -> u32 {
+        0
+    }
+
+    /// Returns the id of the dead state, which is never left and never accepts.
+    #[inline]
+    pub fn dead_state(&self) -> u32 {
+        self.state_names.len() as u32
+    }
+
+    /// Returns the name of the state with the given id, or [None] for the dead state.
+    pub fn state_name(&self, state: u32) -> Option<&str> {
+        self.state_names.get(state as usize).map(String::as_str)
+    }
+
+    /// Returns the symbol with the given id.
+    pub fn symbol(&self, symbol: u32) -> Option<&str> {
+        self.symbols.get(symbol as usize).map(String::as_str)
+    }
+
+    /// Returns the number of symbols in the alphabet.
+    #[inline]
+    pub fn symbol_count(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns the id of `symbol`, if it is part of the alphabet.
+    #[inline]
+    pub fn symbol_id(&self, symbol: &str) -> Option<u32> {
+        match symbol.as_bytes() {
+            [byte] => Some(self.byte_classes[*byte as usize]).filter(|&id| id != NO_SYMBOL),
+            _ => self.other_classes.get(symbol).copied(),
+        }
+    }
+
+    /// Returns the state reached from `state` on the symbol with id `symbol`.
+    #[inline]
+    pub fn step(&self, state: u32, symbol: u32) -> u32 {
+        self.transitions[state as usize * self.symbols.len() + symbol as usize]
+    }
+
+    /// Returns the state reached from `state` on `symbol`, which is the dead state if `symbol`
+    /// is not part of the alphabet.
+    #[inline]
+    pub fn step_symbol(&self, state: u32, symbol: &str) -> u32 {
+        match self.symbol_id(symbol) {
+            Some(symbol) => self.step(state, symbol),
+            None => self.dead_state(),
+        }
+    }
+
+    /// Returns whether the state with the given id is final.
+    #[inline]
+    pub fn is_final(&self, state: u32) -> bool {
+        self.accepting[state as usize]
+    }
+
+    /// Checks if the sequence is accepted by the state machine.
+    pub fn is_accepted<S>(&self, sequence: &[S]) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let mut state = self.initial_state();
+
+        for symbol in sequence.iter() {
+            state = self.step_symbol(state, symbol.as_ref());
+
+            if state == self.dead_state() {
+                return false;
+            }
+        }
+
+        self.is_final(state)
+    }
+
+    /// Checks if the grapheme clusters of `input` form an accepted sequence.
+    pub fn accepts(&self, input: &str) -> bool {
+        let mut state = self.initial_state();
+
+        for grapheme in input.graphemes(true) {
+            state = self.step_symbol(state, grapheme);
+
+            if state == self.dead_state() {
+                return false;
+            }
+        }
+
+        self.is_final(state)
+    }
+
+    /// Returns a runner positioned in the initial state.
+    pub fn runner(&self) -> CompiledRunner<'_> {
+        CompiledRunner { state_machine: self, state: self.initial_state() }
+    }
+}
+
+/// Runs a compiled state machine one symbol at a time, like [Runner](super::Runner).
+#[derive(Clone, Debug)]
+pub struct CompiledRunner<'a> {
+    /// The state machine being run.
+    state_machine: &'a CompiledStateMachine,
+    /// Id of the current state.
+    state: u32,
+}
+
+impl<'a> CompiledRunner<'a> {
+    /// Consumes `symbol` and returns whether a matching transition was found.
+    #[inline]
+    pub fn feed(&mut self, symbol: &str) -> bool {
+        self.state = self.state_machine.step_symbol(self.state, symbol);
+        !self.is_stuck()
+    }
+
+    /// Returns the name of the current state, or [None] if the runner is stuck.
+    pub fn current_state(&self) -> Option<&'a str> {
+        self.state_machine.state_name(self.state)
+    }
+
+    /// Returns whether the symbols fed so far form an accepted sequence.
+    #[inline]
+    pub fn is_accepting(&self) -> bool {
+        self.state_machine.is_final(self.state)
+    }
+
+    /// Returns whether a symbol without a matching transition was fed.
+    #[inline]
+    pub fn is_stuck(&self) -> bool {
+        self.state == self.state_machine.dead_state()
+    }
+
+    /// Moves the runner back to the initial state.
+    pub fn reset(&mut self) {
+        self.state = self.state_machine.initial_state();
+    }
+}
+
+impl StateMachine {
+    /// Compiles the state machine into its dense table form.
+    pub fn compile(&self) -> CompiledStateMachine {
+        let mut state_names = self.sorted_states();
+        state_names.retain(|&state| state != self.initial_state());
+        state_names.insert(0, self.initial_state());
+
+        let symbols = self.sorted_symbols();
+        let state_ids = state_names
+            .iter()
+            .enumerate()
+            .map(|(id, &state)| (state, id as u32))
+            .collect::<HashMap<_, _>>();
+
+        let mut byte_classes = Box::new([NO_SYMBOL; 256]);
+        let mut other_classes = HashMap::new();
+
+        for (id, &symbol) in symbols.iter().enumerate() {
+            match symbol.as_bytes() {
+                [byte] => byte_classes[*byte as usize] = id as u32,
+                _ => {
+                    other_classes.insert(symbol.to_owned(), id as u32);
+                }
+            }
+        }
+
+        let dead_state = state_names.len() as u32;
+        let mut transitions = Vec::with_capacity((state_names.len() + 1) * symbols.len());
+
+        for &state in state_names.iter() {
+            for &symbol in symbols.iter() {
+                let next_state = self.next_state(state, symbol);
+                transitions.push(next_state.map_or(dead_state, |next_state| state_ids[next_state]));
+            }
+        }
+
+        transitions.extend(std::iter::repeat_n(dead_state, symbols.len()));
+
+        let mut accepting =
+            state_names.iter().map(|&state| self.final_states.contains(state)).collect::<Vec<_>>();
+        accepting.push(false);
+
+        CompiledStateMachine {
+            state_names: state_names.into_iter().map(str::to_owned).collect(),
+            symbols: symbols.into_iter().map(str::to_owned).collect(),
+            byte_classes,
+            other_classes,
+            transitions,
+            accepting,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_compile() {
+        let identifier = fixtures::identifier();
+        let compiled = identifier.compile();
+
+        assert_eq!(compiled.state_name(compiled.initial_state()), Some("initial"));
+        assert_eq!(compiled.state_name(compiled.dead_state()), None);
+        assert_eq!(compiled.symbol_id("1"), Some(0));
+        assert_eq!(compiled.symbol_id("b"), None);
+
+        for sequence in identifier.enumerate(4) {
+            assert!(compiled.is_accepted(&sequence));
+        }
+
+        assert!(compiled.accepts("a1_"));
+        assert!(compiled.accepts("__"));
+
+        for input in ["", "_", "1a", "ab", "é"] {
+            assert!(!compiled.accepts(input));
+        }
+    }
+
+    #[test]
+    fn test_multi_byte_symbols() {
+        let state_machine = StateMachine::from_regexp("(é|a)+").unwrap().compile();
+
+        assert!(state_machine.accepts("aéa"));
+        assert!(!state_machine.accepts("ab"));
+
+        let mut runner = state_machine.runner();
+        assert!(runner.feed("é"));
+        assert!(runner.is_accepting());
+        assert!(!runner.feed("e"));
+        assert!(runner.is_stuck());
+        assert!(!runner.feed("a"));
+
+        runner.reset();
+        assert_eq!(runner.current_state(), Some("q0"));
+    }
+}
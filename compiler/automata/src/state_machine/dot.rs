@@ -0,0 +1,120 @@
+use crate::state_machine::StateMachine;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+impl StateMachine {
+    /// Returns the state machine in the Graphviz DOT format.
+    ///
+    /// Final states are drawn as double circles, labelled with their tag if they have one. The
+    /// initial state is marked by an entry arrow and parallel transitions are merged into a
+    /// single edge labelled with all their symbols, each in quotes so that symbols such as `,`
+    /// stay readable.
+    pub fn to_dot(&self) -> String {
+        let mut edges = BTreeMap::<(&str, &str), Vec<&str>>::new();
+        for (src_state, symbol, dst_state) in self.iter_transitions() {
+            edges.entry((src_state, dst_state)).or_default().push(symbol);
+        }
+
+        // The entry arrow starts from an invisible node that must not clash with any state.
+        let mut start_node = String::from("__start");
+        while self.states.contains(&start_node) {
+            start_node.push('_');
+        }
+
+        let mut output = String::new();
+        writeln!(&mut output, "digraph state_machine {{").unwrap();
+        writeln!(&mut output, "    rankdir = LR;").unwrap();
+        writeln!(&mut output, "    {} [shape = point];", quote(&start_node)).unwrap();
+
+        for state in self.sorted_states() {
+            let shape = if self.final_states.contains(state) { "doublecircle" } else { "circle" };
+
+            match self.tag(state) {
+                Some(tag) => writeln!(
+                    &mut output,
+                    "    {} [shape = {}, xlabel = {}];",
+                    quote(state),
+                    shape,
+                    quote(&tag.name),
+                )
+                .unwrap(),
+                None => writeln!(&mut output, "    {} [shape = {}];", quote(state), shape).unwrap(),
+            }
+        }
+
+        writeln!(&mut output, "    {} -> {};", quote(&start_node), quote(&self.initial_state))
+            .unwrap();
+
+        for ((src_state, dst_state), mut symbols) in edges {
+            symbols.sort_unstable();
+            let label = symbols.into_iter().map(quote).collect::<Vec<_>>().join(", ");
+
+            writeln!(
+                &mut output,
+                "    {} -> {} [label = {}];",
+                quote(src_state),
+                quote(dst_state),
+                quote(&label),
+            )
+            .unwrap();
+        }
+
+        writeln!(&mut output, "}}").unwrap();
+        output
+    }
+}
+
+/// Turns `id` into a quoted DOT identifier.
+fn quote(id: &str) -> String {
+    let mut quoted = String::with_capacity(id.len() + 2);
+    quoted.push('"');
+
+    for c in id.chars() {
+        if matches!(c, '"' | '\\') {
+            quoted.push('\\');
+        }
+
+        quoted.push(c);
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_to_dot() {
+        let state_machine = fixtures::number();
+
+        let expected = r#"digraph state_machine {
+    rankdir = LR;
+    "__start" [shape = point];
+    "final" [shape = doublecircle];
+    "initial" [shape = circle];
+    "__start" -> "initial";
+    "final" -> "final" [label = "\"0\", \"1\", \"2\""];
+    "initial" -> "final" [label = "\"1\", \"2\""];
+}
+"#;
+
+        assert_eq!(state_machine.to_dot(), expected);
+    }
+
+    #[test]
+    fn test_to_dot_symbols() {
+        let state_machine = StateMachine::from_regexp(r#"[,"]"#).unwrap();
+        let label = r#"[label = "\"\\\"\", \",\""];"#;
+
+        assert!(state_machine.to_dot().contains(label), "{}", state_machine.to_dot());
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("final"), r#""final""#);
+        assert_eq!(quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
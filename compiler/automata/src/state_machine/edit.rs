@@ -0,0 +1,104 @@
+use crate::state_machine::{StateMachine, StateMachineDeserError, Tag};
+use std::sync::Arc;
+
+impl StateMachine {
+    /// Adds a state without any transitions. Returns whether the state was added, which is not
+    /// the case if it already exists.
+    pub fn add_state(&mut self, state: &str) -> bool {
+        self.states.insert(Arc::new(state.to_owned()))
+    }
+
+    /// Adds a symbol to the alphabet. Returns whether the symbol was added, which is not the
+    /// case if it already exists.
+    pub fn add_symbol(&mut self, symbol: &str) -> bool {
+        self.alphabet.insert(Arc::new(symbol.to_owned()))
+    }
+
+    /// Adds a transition from `src_state` to `dst_state` on `symbol`, adding `symbol` to the
+    /// alphabet if needed. Returns the destination of the transition it replaces, if any.
+    pub fn add_transition(
+        &mut self,
+        src_state: &str,
+        symbol: &str,
+        dst_state: &str,
+    ) -> Result<Option<String>, StateMachineDeserError> {
+        self.find_state(src_state)?;
+        let dst_state = self.find_state(dst_state)?;
+
+        self.add_symbol(symbol);
+
+        let previous = self
+            .transitions
+            .entry(src_state.to_owned())
+            .or_default()
+            .insert(symbol.to_owned(), dst_state);
+
+        Ok(previous.map(|state| state.as_str().to_owned()))
+    }
+
+    /// Marks `state` as final or non-final. Marking a state as non-final removes its tag.
+    pub fn set_final(&mut self, state: &str, is_final: bool) -> Result<(), StateMachineDeserError> {
+        self.find_state(state)?;
+
+        if is_final {
+            self.final_states.insert(state.to_owned());
+        } else {
+            self.final_states.remove(state);
+            self.tags.remove(state);
+        }
+
+        Ok(())
+    }
+
+    /// Sets or removes the tag of `state`, which must be final.
+    pub fn set_tag(&mut self, state: &str, tag: Option<Tag>) -> Result<(), StateMachineDeserError> {
+        self.find_state(state)?;
+
+        if !self.final_states.contains(state) {
+            return Err(StateMachineDeserError::NonFinalTag(state.to_owned()));
+        }
+
+        match tag {
+            Some(tag) => self.tags.insert(state.to_owned(), tag),
+            None => self.tags.remove(state),
+        };
+
+        Ok(())
+    }
+
+    /// Returns the shared name of `state`, or an error if it does not exist.
+    fn find_state(&self, state: &str) -> Result<Arc<String>, StateMachineDeserError> {
+        self.states
+            .get(&String::from(state))
+            .cloned()
+            .ok_or_else(|| StateMachineDeserError::InvalidState(state.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit() {
+        let mut state_machine = StateMachine::from_fa_str("p\na\np\n\n").unwrap();
+
+        assert!(state_machine.add_state("q"));
+        assert!(!state_machine.add_state("q"));
+        assert_eq!(state_machine.add_transition("p", "a", "q").unwrap(), None);
+        assert_eq!(state_machine.add_transition("p", "a", "p").unwrap(), Some("q".to_owned()));
+        assert_eq!(state_machine.add_transition("p", " ", "q").unwrap(), None);
+        assert!(state_machine.add_transition("p", "a", "r").is_err());
+
+        state_machine.set_final("q", true).unwrap();
+        assert!(state_machine.set_final("r", true).is_err());
+
+        assert!(state_machine.set_tag("p", Some(Tag::new("p", 0))).is_err());
+        state_machine.set_tag("q", Some(Tag::new("q", 0))).unwrap();
+        assert_eq!(state_machine.classify(&["a", " "]), Some(&Tag::new("q", 0)));
+
+        assert!(state_machine.is_accepted(&["a", " "]));
+        assert!(!state_machine.is_accepted(&["a"]));
+        assert_eq!(state_machine.sorted_symbols(), [" ", "a"]);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::state_machine::{Regexp, StateMachine};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Edges of the generalized state machine used during state elimination, labelled with
+/// regular expressions.
+type Edges = BTreeMap<(usize, usize), Regexp>;
+
+impl StateMachine {
+    /// Returns a regular expression accepting the same language, built using the state
+    /// elimination method on the minimized state machine.
+    pub fn to_regexp(&self) -> Regexp {
+        let machine = self.minimize();
+        let states = machine.sorted_states();
+        let indices =
+            states.iter().enumerate().map(|(i, &state)| (state, i)).collect::<HashMap<_, _>>();
+
+        // New initial and final states, linked to the old ones through epsilon transitions.
+        let start = states.len();
+        let end = start + 1;
+
+        let mut edges = Edges::new();
+        add_edge(&mut edges, start, indices[machine.initial_state()], Regexp::Epsilon);
+
+        for state in machine.final_states() {
+            add_edge(&mut edges, indices[state], end, Regexp::Epsilon);
+        }
+
+        let mut transitions = machine.iter_transitions().collect::<Vec<_>>();
+        transitions.sort_unstable();
+
+        for (src_state, symbol, dst_state) in transitions {
+            let symbol = Regexp::Symbol(symbol.to_owned());
+            add_edge(&mut edges, indices[src_state], indices[dst_state], symbol);
+        }
+
+        let mut remaining = (0..states.len()).collect::<BTreeSet<_>>();
+
+        while !remaining.is_empty() {
+            // Eliminating the state with the fewest paths through it keeps the result small.
+            let state = remaining
+                .iter()
+                .copied()
+                .min_by_key(|&state| {
+                    let incoming = edges.keys().filter(|&&(p, q)| q == state && p != state);
+                    let outgoing = edges.keys().filter(|&&(p, q)| p == state && q != state);
+                    incoming.count() * outgoing.count()
+                })
+                .unwrap();
+
+            remaining.remove(&state);
+
+            let self_loop = edges.remove(&(state, state)).map_or(Regexp::Epsilon, Regexp::star);
+
+            let incoming = take_edges(&mut edges, |&(_, dst_state)| dst_state == state);
+            let outgoing = take_edges(&mut edges, |&(src_state, _)| src_state == state);
+
+            for ((src_state, _), incoming_regexp) in incoming.iter() {
+                for ((_, dst_state), outgoing_regexp) in outgoing.iter() {
+                    let regexp = incoming_regexp
+                        .clone()
+                        .concat(self_loop.clone())
+                        .concat(outgoing_regexp.clone());
+
+                    add_edge(&mut edges, *src_state, *dst_state, regexp);
+                }
+            }
+        }
+
+        edges.remove(&(start, end)).unwrap_or(Regexp::Empty)
+    }
+}
+
+/// Adds an edge labelled with `regexp`, merging it with any existing edge between the same
+/// states.
+fn add_edge(edges: &mut Edges, src_state: usize, dst_state: usize, regexp: Regexp) {
+    let existing = edges.remove(&(src_state, dst_state)).unwrap_or(Regexp::Empty);
+    edges.insert((src_state, dst_state), existing.alternation(regexp));
+}
+
+/// Removes and returns all edges whose `(src_state, dst_state)` key matches `predicate`.
+fn take_edges<P>(edges: &mut Edges, predicate: P) -> Vec<((usize, usize), Regexp)>
+where
+    P: Fn(&(usize, usize)) -> bool,
+{
+    let keys = edges.keys().filter(|key| predicate(key)).copied().collect::<Vec<_>>();
+    keys.into_iter().map(|key| (key, edges.remove(&key).unwrap())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    fn round_trip(state_machine: &StateMachine) -> String {
+        let regexp = state_machine.to_regexp().to_string();
+        let compiled = StateMachine::from_regexp(&regexp).unwrap();
+        assert_eq!(compiled.equivalent(state_machine), Ok(()), "{} is not equivalent", regexp);
+        regexp
+    }
+
+    #[test]
+    fn test_to_regexp() {
+        let number = fixtures::number();
+        assert_eq!(round_trip(&number), "[12][0-2]*");
+
+        let identifier = fixtures::identifier();
+        assert_eq!(round_trip(&identifier), "(a|_[1_a])[1_a]*");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let patterns = ["", "a?b", "(ab|c)*", "a(b|c)+d?", "[a-e]|x*y", "((a|b)c)*|\\*"];
+
+        for pattern in patterns {
+            round_trip(&StateMachine::from_regexp(pattern).unwrap());
+        }
+
+        let empty = StateMachine::from_fa_str("a b\n0\na\nb\na 0 a\n").unwrap();
+        assert_eq!(empty.to_regexp(), Regexp::Empty);
+    }
+
+    #[test]
+    fn test_simplifications() {
+        let a = || Regexp::Symbol("a".to_owned());
+
+        assert_eq!(Regexp::Epsilon.concat(a()), a());
+        assert_eq!(Regexp::Empty.concat(a()), Regexp::Empty);
+        assert_eq!(a().concat(a().star()), Regexp::Plus(Box::new(a())));
+        assert_eq!(Regexp::Empty.alternation(a()), a());
+        assert_eq!(a().alternation(a()), a());
+        assert_eq!(Regexp::Epsilon.alternation(a()), Regexp::Optional(Box::new(a())));
+        assert_eq!(Regexp::Epsilon.alternation(a().star()), a().star());
+        assert_eq!(a().star().star(), a().star());
+        assert_eq!(Regexp::parse("(a)").unwrap().to_string(), "a");
+    }
+}
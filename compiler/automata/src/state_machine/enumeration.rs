@@ -0,0 +1,203 @@
+use crate::state_machine::StateMachine;
+use crate::utils::Rng;
+use std::collections::HashMap;
+
+impl StateMachine {
+    /// Returns all accepted sequences of length at most `max_len`, in shortlex order: shorter
+    /// sequences first, sequences of the same length sorted alphabetically by symbol.
+    pub fn enumerate(&self, max_len: usize) -> Vec<Vec<String>> {
+        let symbols = self.sorted_symbols();
+        let productive = &self.productive_states();
+
+        let mut accepted = Vec::new();
+        let mut current = Vec::<(&str, Vec<&str>)>::new();
+
+        if productive.contains(self.initial_state()) {
+            current.push((self.initial_state(), Vec::new()));
+        }
+
+        for len in 0..=max_len {
+            for (state, sequence) in current.iter() {
+                if self.final_states.contains(*state) {
+                    accepted.push(sequence.iter().map(|&symbol| symbol.to_owned()).collect());
+                }
+            }
+
+            if len == max_len {
+                break;
+            }
+
+            // Extending the sequences in order keeps the next level sorted.
+            current = current
+                .iter()
+                .flat_map(|(state, sequence)| {
+                    symbols.iter().filter_map(move |&symbol| {
+                        let next_state = self.next_state(state, symbol)?;
+
+                        productive.contains(next_state).then(|| {
+                            let mut next_sequence = sequence.clone();
+                            next_sequence.push(symbol);
+                            (next_state, next_sequence)
+                        })
+                    })
+                })
+                .collect();
+        }
+
+        accepted
+    }
+
+    /// Returns the number of accepted sequences of each length from `0` to `max_len`. Counts
+    /// saturate at [u128::MAX].
+    pub fn count_accepted(&self, max_len: usize) -> Vec<u128> {
+        let mut counts = Vec::with_capacity(max_len + 1);
+        let mut paths = HashMap::<&str, u128>::from([(self.initial_state(), 1)]);
+
+        for len in 0..=max_len {
+            counts.push(
+                paths
+                    .iter()
+                    .filter(|(state, _)| self.final_states.contains(**state))
+                    .fold(0_u128, |count, (_, paths)| count.saturating_add(*paths)),
+            );
+
+            if len == max_len {
+                break;
+            }
+
+            let mut next_paths = HashMap::<&str, u128>::new();
+            for (state, count) in paths {
+                for (_, next_state) in self.transitions.get(state).into_iter().flatten() {
+                    let next_count = next_paths.entry(next_state.as_str()).or_default();
+                    *next_count = next_count.saturating_add(count);
+                }
+            }
+
+            paths = next_paths;
+        }
+
+        counts
+    }
+
+    /// Returns a random accepted sequence of length `len`, chosen uniformly among all accepted
+    /// sequences of that length, or [None] if there are no such sequences or too many to count
+    /// in a [u128].
+    pub fn sample(&self, len: usize, rng: &mut Rng) -> Option<Vec<String>> {
+        let symbols = self.sorted_symbols();
+
+        // `suffixes[n][state]` is the number of sequences of length `n` accepted from `state`.
+        let mut suffixes =
+            vec![self.final_states().map(|state| (state, 1_u128)).collect::<HashMap<_, _>>()];
+
+        for n in 1..=len {
+            let previous = &suffixes[n - 1];
+            let mut counts = HashMap::<&str, u128>::new();
+
+            for state in self.iter_states() {
+                let count = symbols
+                    .iter()
+                    .filter_map(|symbol| self.next_state(state, symbol))
+                    .filter_map(|next_state| previous.get(next_state))
+                    .try_fold(0_u128, |count, next_count| count.checked_add(*next_count))?;
+
+                if count != 0 {
+                    counts.insert(state, count);
+                }
+            }
+
+            suffixes.push(counts);
+        }
+
+        let mut state = self.initial_state();
+        let mut remaining = *suffixes[len].get(state)?;
+        let mut sequence = Vec::with_capacity(len);
+
+        // Pick each symbol with a probability proportional to the number of accepted sequences
+        // continuing with it.
+        for n in (0..len).rev() {
+            let mut choice = rng.below(remaining);
+
+            for &symbol in symbols.iter() {
+                let next_state = match self.next_state(state, symbol) {
+                    Some(next_state) => next_state,
+                    None => continue,
+                };
+
+                let count = suffixes[n].get(next_state).copied().unwrap_or_default();
+
+                if choice < count {
+                    sequence.push(symbol.to_owned());
+                    state = next_state;
+                    remaining = count;
+                    break;
+                }
+
+                choice -= count;
+            }
+        }
+
+        Some(sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_enumerate() {
+        let words = fixtures::number()
+            .enumerate(2)
+            .into_iter()
+            .map(|sequence| sequence.concat())
+            .collect::<Vec<_>>();
+
+        assert_eq!(words, ["1", "2", "10", "11", "12", "20", "21", "22"]);
+    }
+
+    #[test]
+    fn test_count_accepted() {
+        assert_eq!(fixtures::number().count_accepted(3), vec![0, 2, 6, 18]);
+
+        let identifier = fixtures::identifier();
+
+        let counts = identifier.count_accepted(4);
+        for (len, count) in counts.into_iter().enumerate() {
+            assert_eq!(
+                count,
+                identifier.enumerate(len).iter().filter(|s| s.len() == len).count() as u128
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample() {
+        let state_machine = fixtures::number();
+        let mut rng = Rng::new(7);
+
+        let mut seen = Vec::new();
+        for _ in 0..200 {
+            let sequence = state_machine.sample(2, &mut rng).unwrap();
+            assert_eq!(sequence.len(), 2);
+            assert!(state_machine.is_accepted(&sequence));
+
+            if !seen.contains(&sequence) {
+                seen.push(sequence);
+            }
+        }
+
+        // All 6 accepted sequences of length 2 show up.
+        assert_eq!(seen.len(), 6);
+        assert_eq!(state_machine.sample(0, &mut rng), None);
+    }
+
+    #[test]
+    fn test_sample_too_many() {
+        let state_machine = StateMachine::from_regexp("[ab]*").unwrap();
+        let mut rng = Rng::new(7);
+
+        assert_eq!(state_machine.sample(127, &mut rng).map(|sequence| sequence.len()), Some(127));
+        assert_eq!(state_machine.sample(128, &mut rng), None);
+    }
+}
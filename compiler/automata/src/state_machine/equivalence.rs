@@ -0,0 +1,90 @@
+use crate::state_machine::StateMachine;
+
+/// Shortest sequence on which two state machines disagree.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Counterexample {
+    /// The distinguishing sequence. Ties between sequences of the same length are broken
+    /// alphabetically.
+    pub sequence: Vec<String>,
+    /// Whether the sequence is accepted by the state machine the comparison was called on. The
+    /// other state machine gives the opposite answer.
+    pub accepted_by_self: bool,
+}
+
+impl StateMachine {
+    /// Checks whether `self` and `other` accept the same language. Returns the shortest
+    /// sequence accepted by only one of them otherwise.
+    pub fn equivalent(&self, other: &StateMachine) -> Result<(), Counterexample> {
+        match self.symmetric_difference(other).shortest_accepted() {
+            Some(sequence) => {
+                let accepted_by_self = self.is_accepted(&sequence);
+                Err(Counterexample { sequence, accepted_by_self })
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Checks whether every sequence accepted by `self` is also accepted by `other`. Returns the
+    /// shortest sequence accepted by `self`, but not by `other` otherwise.
+    pub fn is_subset_of(&self, other: &StateMachine) -> Result<(), Counterexample> {
+        match self.difference(other).shortest_accepted() {
+            Some(sequence) => Err(Counterexample { sequence, accepted_by_self: true }),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_equivalent() {
+        let identifier = fixtures::identifier();
+
+        // Same language, but with differently named and duplicated states.
+        let renamed = StateMachine::from_fa_str(
+            "s u f g\n_ a 1\ns\nf g\n\
+             s _ u\ns a f\nu _ f\nu a f\nu 1 f\nf _ g\nf a g\nf 1 g\ng _ f\ng a f\ng 1 f\n",
+        )
+        .unwrap();
+
+        assert_eq!(identifier.equivalent(&renamed), Ok(()));
+        assert_eq!(renamed.equivalent(&identifier), Ok(()));
+    }
+
+    #[test]
+    fn test_not_equivalent() {
+        let identifier = fixtures::identifier();
+        let letters = StateMachine::from_fa_str("s f\na\ns\nf\ns a f\nf a f\n").unwrap();
+
+        assert_eq!(
+            identifier.equivalent(&letters),
+            Err(Counterexample {
+                sequence: vec!["_".to_owned(), "1".to_owned()],
+                accepted_by_self: true
+            }),
+        );
+
+        assert_eq!(
+            letters.equivalent(&identifier),
+            Err(Counterexample {
+                sequence: vec!["_".to_owned(), "1".to_owned()],
+                accepted_by_self: false
+            }),
+        );
+    }
+
+    #[test]
+    fn test_is_subset_of() {
+        let identifier = fixtures::identifier();
+        let letters = StateMachine::from_fa_str("s f\na\ns\nf\ns a f\nf a f\n").unwrap();
+
+        assert_eq!(letters.is_subset_of(&identifier), Ok(()));
+        assert_eq!(
+            identifier.is_subset_of(&letters).unwrap_err().sequence,
+            vec!["_".to_owned(), "1".to_owned()],
+        );
+    }
+}
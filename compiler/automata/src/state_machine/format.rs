@@ -0,0 +1,211 @@
+use crate::state_machine::{
+    SerializedStateMachine, SerializedTrans, StateMachine, StateMachineDeserError,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Format in which a state machine is stored.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StateMachineFormat {
+    /// JSON object with `states`, `alphabet`, `transitions`, `initial_state` and `final_states`.
+    Json,
+    /// Line-oriented `FA.in` text format.
+    ///
+    /// The first four lines contain the states, the alphabet, the initial state and the final
+    /// states, separated by whitespace. Every following line contains a transition of the shape
+    /// `src_state symbol dst_state`.
+    Fa,
+}
+
+impl StateMachineFormat {
+    /// Returns the format of the file at `path`. Files with the `.in` extension use the `FA.in`
+    /// format, all other files are read as JSON.
+    pub fn from_path<P>(path: P) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        match path.as_ref().extension().and_then(|extension| extension.to_str()) {
+            Some("in") => Self::Fa,
+            _ => Self::Json,
+        }
+    }
+}
+
+impl StateMachine {
+    /// Parses a state machine stored in the given format.
+    pub fn parse(input: &str, format: StateMachineFormat) -> Result<Self, Box<dyn Error>> {
+        Ok(match format {
+            StateMachineFormat::Json => serde_json::from_str::<StateMachine>(input)?,
+            StateMachineFormat::Fa => Self::from_fa_str(input)?,
+        })
+    }
+
+    /// Returns the state machine stored in the given format.
+    pub fn to_string_with_format(&self, format: StateMachineFormat) -> String {
+        match format {
+            StateMachineFormat::Json => self.to_json_string(),
+            StateMachineFormat::Fa => self.to_fa_string(),
+        }
+    }
+
+    /// Returns the state machine as pretty-printed JSON, with states, symbols and transitions
+    /// sorted.
+    pub fn to_json_string(&self) -> String {
+        let mut output = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+
+        self.serialize(&mut serializer).expect("Failed to serialize state machine");
+        output.push(b'\n');
+
+        String::from_utf8(output).expect("Serialized state machine is not valid UTF-8")
+    }
+
+    /// Parses a state machine stored in the `FA.in` format.
+    pub fn from_fa_str(input: &str) -> Result<Self, StateMachineDeserError> {
+        let mut lines = input.lines();
+
+        let mut next_line = |name: &'static str| {
+            lines
+                .next()
+                .map(|line| line.split_whitespace().map(str::to_owned).collect::<Vec<_>>())
+                .ok_or(StateMachineDeserError::MissingLine(name))
+        };
+
+        let states = next_line("states")?;
+        let alphabet = next_line("alphabet")?;
+        let initial_state = match next_line("initial state")?.as_slice() {
+            [initial_state] => initial_state.clone(),
+            tokens => {
+                return Err(StateMachineDeserError::NotSingleToken("initial state", tokens.len()))
+            }
+        };
+        let final_states = next_line("final states")?;
+
+        let mut transitions = Vec::new();
+
+        // The header takes up the first four lines.
+        for (i, line) in input.lines().enumerate().skip(4) {
+            let transition = line.split_whitespace().collect::<Vec<_>>();
+
+            match transition.as_slice() {
+                [] => (),
+                [src_state, symbol, dst_state] => transitions.push(SerializedTrans {
+                    src_state: src_state.to_string(),
+                    symbol: symbol.to_string(),
+                    dst_state: dst_state.to_string(),
+                }),
+                _ => return Err(StateMachineDeserError::InvalidTransition(i + 1)),
+            }
+        }
+
+        StateMachine::try_from(SerializedStateMachine {
+            states: states.into_iter().collect(),
+            alphabet: alphabet.into_iter().collect(),
+            transitions: transitions.into_iter().collect(),
+            initial_state,
+            final_states: final_states.into_iter().collect(),
+            tags: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the state machine in the `FA.in` format, with states, symbols and transitions
+    /// sorted. States and symbols containing whitespace cannot be represented in this format, and
+    /// tags are left out.
+    pub fn to_fa_string(&self) -> String {
+        let machine = SerializedStateMachine::from(self);
+        let join = |items: &BTreeSet<String>| items.iter().cloned().collect::<Vec<_>>().join(" ");
+
+        let mut output = String::new();
+        writeln!(&mut output, "{}", join(&machine.states)).unwrap();
+        writeln!(&mut output, "{}", join(&machine.alphabet)).unwrap();
+        writeln!(&mut output, "{}", machine.initial_state).unwrap();
+        writeln!(&mut output, "{}", join(&machine.final_states)).unwrap();
+
+        for transition in machine.transitions.iter() {
+            writeln!(
+                &mut output,
+                "{} {} {}",
+                transition.src_state, transition.symbol, transition.dst_state
+            )
+            .unwrap();
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    const NUMBER_FA: &str = "final initial
+0 1 2
+initial
+final
+final 0 final
+final 1 final
+final 2 final
+initial 1 final
+initial 2 final
+";
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(StateMachineFormat::from_path("FA.in"), StateMachineFormat::Fa);
+        assert_eq!(StateMachineFormat::from_path("number.json"), StateMachineFormat::Json);
+        assert_eq!(StateMachineFormat::from_path("number"), StateMachineFormat::Json);
+    }
+
+    #[test]
+    fn test_fa_round_trip() {
+        let state_machine = fixtures::number();
+
+        assert_eq!(state_machine.to_fa_string(), NUMBER_FA);
+
+        let parsed = StateMachine::from_fa_str(NUMBER_FA).unwrap();
+        assert_eq!(parsed.to_fa_string(), NUMBER_FA);
+        assert!(parsed.is_accepted(&["1", "0"]));
+        assert!(!parsed.is_accepted(&["0", "1"]));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let state_machine = StateMachine::from_fa_str(NUMBER_FA).unwrap();
+        let json = state_machine.to_json_string();
+
+        let parsed = StateMachine::parse(&json, StateMachineFormat::Json).unwrap();
+        assert_eq!(parsed.to_json_string(), json);
+        assert_eq!(parsed.to_fa_string(), NUMBER_FA);
+    }
+
+    #[test]
+    fn test_fa_errors() {
+        assert!(matches!(
+            StateMachine::from_fa_str("a b\n0 1\n"),
+            Err(StateMachineDeserError::MissingLine("initial state")),
+        ));
+
+        assert!(matches!(
+            StateMachine::from_fa_str("a b\n0\na b\nb\n"),
+            Err(StateMachineDeserError::NotSingleToken("initial state", 2)),
+        ));
+
+        let error = StateMachine::from_fa_str("a b\n0\n\nb\n").err().unwrap();
+        assert_eq!(error.to_string(), "Expected a single token on the initial state line, found 0");
+
+        assert!(matches!(
+            StateMachine::from_fa_str("a b\n0\na\nb\na 0\n"),
+            Err(StateMachineDeserError::InvalidTransition(5)),
+        ));
+
+        assert!(matches!(
+            StateMachine::from_fa_str("a b\n0\na\nb\na 1 b\n"),
+            Err(StateMachineDeserError::InvalidSymbol(_)),
+        ));
+    }
+}
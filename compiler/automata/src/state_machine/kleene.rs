@@ -0,0 +1,188 @@
+use crate::state_machine::{Nfa, StateMachine};
+
+impl Nfa {
+    /// Returns a state machine accepting the sequences of `self` followed by those of `other`.
+    pub fn concat(&self, other: &Nfa) -> Nfa {
+        let mut nfa = Nfa::new();
+        let (start, ends) = nfa.append(self);
+        let (other_start, other_ends) = nfa.append(other);
+
+        nfa.add_transition(nfa.initial_state(), None, start);
+        for end in ends {
+            nfa.add_transition(end, None, other_start);
+        }
+
+        for end in other_ends {
+            nfa.set_final(end, true);
+        }
+
+        nfa
+    }
+
+    /// Returns a state machine accepting the sequences of either `self` or `other`.
+    pub fn union(&self, other: &Nfa) -> Nfa {
+        let mut nfa = Nfa::new();
+
+        for operand in [self, other] {
+            let (start, ends) = nfa.append(operand);
+            nfa.add_transition(nfa.initial_state(), None, start);
+
+            for end in ends {
+                nfa.set_final(end, true);
+            }
+        }
+
+        nfa
+    }
+
+    /// Returns a state machine accepting any number of sequences of `self`, one after the other.
+    pub fn star(&self) -> Nfa {
+        let mut nfa = self.plus();
+        nfa.set_final(nfa.initial_state(), true);
+        nfa
+    }
+
+    /// Returns a state machine accepting one or more sequences of `self`, one after the other.
+    pub fn plus(&self) -> Nfa {
+        let mut nfa = Nfa::new();
+        let (start, ends) = nfa.append(self);
+
+        nfa.add_transition(nfa.initial_state(), None, start);
+        for end in ends {
+            nfa.add_transition(end, None, start);
+            nfa.set_final(end, true);
+        }
+
+        nfa
+    }
+
+    /// Returns a state machine accepting the sequences of `self` and the empty sequence.
+    pub fn optional(&self) -> Nfa {
+        let mut nfa = self.clone();
+        let initial_state = nfa.add_state();
+
+        nfa.add_transition(initial_state, None, self.initial_state());
+        nfa.set_initial_state(initial_state);
+        nfa.set_final(initial_state, true);
+        nfa
+    }
+
+    /// Returns a state machine accepting the sequences of `self` read backwards.
+    pub fn reverse(&self) -> Nfa {
+        let mut nfa = Nfa::new();
+        let offset = nfa.state_count();
+
+        for _ in 0..self.state_count() {
+            nfa.add_state();
+        }
+
+        for (src_state, symbol, dst_state) in self.iter_transitions() {
+            nfa.add_transition(dst_state + offset, symbol, src_state + offset);
+        }
+
+        for state in self.final_states() {
+            nfa.add_transition(nfa.initial_state(), None, state + offset);
+        }
+
+        nfa.set_final(self.initial_state() + offset, true);
+        nfa
+    }
+
+    /// Copies the states, transitions and tags of `other`, without marking any state as final.
+    /// Returns the copies of its initial state and of its final states.
+    fn append(&mut self, other: &Nfa) -> (usize, Vec<usize>) {
+        let offset = self.state_count();
+
+        for _ in 0..other.state_count() {
+            self.add_state();
+        }
+
+        for (src_state, symbol, dst_state) in other.iter_transitions() {
+            self.add_transition(src_state + offset, symbol, dst_state + offset);
+        }
+
+        for state in other.final_states() {
+            self.set_tag(state + offset, other.tag(state).cloned());
+        }
+
+        let ends = other.final_states().map(|state| state + offset).collect();
+        (other.initial_state() + offset, ends)
+    }
+}
+
+impl StateMachine {
+    /// Returns a state machine accepting the sequences of `self` followed by those of `other`.
+    pub fn concat(&self, other: &StateMachine) -> Nfa {
+        Nfa::from(self).concat(&Nfa::from(other))
+    }
+
+    /// Returns a nondeterministic state machine accepting the sequences of either `self` or
+    /// `other`. Unlike [StateMachine::union], the result can be combined further before being
+    /// determinized.
+    pub fn union_nfa(&self, other: &StateMachine) -> Nfa {
+        Nfa::from(self).union(&Nfa::from(other))
+    }
+
+    /// Returns a state machine accepting any number of sequences of `self`, one after the other.
+    pub fn star(&self) -> Nfa {
+        Nfa::from(self).star()
+    }
+
+    /// Returns a state machine accepting one or more sequences of `self`, one after the other.
+    pub fn plus(&self) -> Nfa {
+        Nfa::from(self).plus()
+    }
+
+    /// Returns a state machine accepting the sequences of `self` and the empty sequence.
+    pub fn optional(&self) -> Nfa {
+        Nfa::from(self).optional()
+    }
+
+    /// Returns a state machine accepting the sequences of `self` read backwards.
+    pub fn reverse(&self) -> Nfa {
+        Nfa::from(self).reverse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    fn regexp(pattern: &str) -> StateMachine {
+        StateMachine::from_regexp(pattern).unwrap()
+    }
+
+    fn assert_equivalent(nfa: Nfa, pattern: &str) {
+        let state_machine = nfa.to_state_machine().minimize();
+        assert_eq!(state_machine.equivalent(&regexp(pattern)), Ok(()), "{}", pattern);
+    }
+
+    #[test]
+    fn test_kleene_operations() {
+        let ab = regexp("ab");
+        let c = regexp("c|");
+
+        assert_equivalent(ab.concat(&c), "abc?");
+        assert_equivalent(ab.union_nfa(&c), "ab|c?");
+        assert_equivalent(ab.star(), "(ab)*");
+        assert_equivalent(c.plus(), "c*");
+        assert_equivalent(ab.optional(), "(ab)?");
+        assert_equivalent(regexp("a(b|cd)*").reverse(), "(b|dc)*a");
+        assert_equivalent(regexp("").star(), "()");
+    }
+
+    #[test]
+    fn test_identifier_from_parts() {
+        let letter = Nfa::from(&regexp("a"));
+        let ident_start = Nfa::from(&regexp("_|a"));
+        let ident_char = Nfa::from(&regexp("_|a|1"));
+
+        // <ident> ::= <letter> | <ident_start><ident_char_seq>
+        let ident = letter.union(&ident_start.concat(&ident_char.plus()));
+
+        let identifier = fixtures::identifier();
+
+        assert_eq!(ident.to_state_machine().minimize().equivalent(&identifier), Ok(()));
+    }
+}
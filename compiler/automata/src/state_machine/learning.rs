@@ -0,0 +1,299 @@
+use crate::state_machine::{
+    SerializedStateMachine, SerializedTrans, StateMachine, TestCase, TestCaseError,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Teacher answering the queries asked by [StateMachine::learn] about an unknown language.
+pub trait Oracle {
+    /// Returns the symbols the words of the language are made of.
+    fn alphabet(&self) -> Vec<String>;
+
+    /// Returns whether `word` belongs to the language.
+    fn is_member(&self, word: &[String]) -> bool;
+
+    /// Returns a word on which `hypothesis` and the language disagree, or `None` if the
+    /// hypothesis accepts exactly the language.
+    fn find_counterexample(&self, hypothesis: &StateMachine) -> Option<Vec<String>>;
+}
+
+/// The language of a state machine, answering equivalence queries exactly.
+impl Oracle for StateMachine {
+    fn alphabet(&self) -> Vec<String> {
+        self.sorted_symbols().into_iter().map(str::to_owned).collect()
+    }
+
+    fn is_member(&self, word: &[String]) -> bool {
+        self.is_accepted(word)
+    }
+
+    fn find_counterexample(&self, hypothesis: &StateMachine) -> Option<Vec<String>> {
+        self.equivalent(hypothesis).err().map(|counterexample| counterexample.sequence)
+    }
+}
+
+/// Oracle backed by labelled examples, such as those of a cases file. Words without a label are
+/// treated as rejected, so the learned state machine accepts exactly the words labelled
+/// `accept`.
+#[derive(Clone, Debug)]
+pub struct ExampleOracle {
+    /// Each example, split into grapheme clusters, along with whether it should be accepted.
+    examples: Vec<(Vec<String>, bool)>,
+    /// Labels of the examples, by word.
+    labels: HashMap<Vec<String>, bool>,
+}
+
+impl ExampleOracle {
+    /// Creates an oracle from the given cases. Returns an error if a word is labelled both
+    /// `accept` and `reject`.
+    pub fn new(cases: &[TestCase]) -> Result<Self, TestCaseError> {
+        let mut examples = Vec::new();
+        let mut labels = HashMap::<Vec<String>, (bool, usize)>::new();
+
+        for case in cases {
+            let word = case.word.graphemes(true).map(str::to_owned).collect::<Vec<_>>();
+
+            match labels.get(&word) {
+                Some(&(should_accept, line)) if should_accept != case.should_accept => {
+                    return Err(TestCaseError::new(
+                        format!("\"{}\" is labelled differently on line {}", case.word, line),
+                        case.line,
+                    ));
+                }
+                Some(_) => (),
+                None => {
+                    labels.insert(word.clone(), (case.should_accept, case.line));
+                    examples.push((word, case.should_accept));
+                }
+            }
+        }
+
+        let labels =
+            labels.into_iter().map(|(word, (should_accept, _))| (word, should_accept)).collect();
+
+        Ok(Self { examples, labels })
+    }
+}
+
+impl Oracle for ExampleOracle {
+    fn alphabet(&self) -> Vec<String> {
+        let symbols = self.examples.iter().flat_map(|(word, _)| word).collect::<BTreeSet<_>>();
+        symbols.into_iter().cloned().collect()
+    }
+
+    fn is_member(&self, word: &[String]) -> bool {
+        self.labels.get(word).copied().unwrap_or(false)
+    }
+
+    fn find_counterexample(&self, hypothesis: &StateMachine) -> Option<Vec<String>> {
+        self.examples
+            .iter()
+            .find(|(word, should_accept)| hypothesis.is_accepted(word) != *should_accept)
+            .map(|(word, _)| word.clone())
+    }
+}
+
+/// State machine learned from an oracle, along with the number of queries it took.
+pub struct Learned {
+    /// The minimal state machine accepting the language of the oracle.
+    pub state_machine: StateMachine,
+    /// Number of distinct words whose membership was queried.
+    pub membership_queries: usize,
+    /// Number of hypotheses submitted to the oracle, including the final one.
+    pub equivalence_queries: usize,
+}
+
+impl StateMachine {
+    /// Learns the language of `oracle` using Angluin's L* algorithm.
+    ///
+    /// Counterexamples are handled by adding all of their suffixes to the observation table, as
+    /// described by Maler and Pnueli, which keeps the table consistent. The result is minimized,
+    /// so its states are named `q0`, `q1`, ... like those of [StateMachine::minimize].
+    pub fn learn<O>(oracle: &O) -> Learned
+    where
+        O: Oracle + ?Sized,
+    {
+        let mut table = ObservationTable::new(oracle);
+        let mut equivalence_queries = 0;
+
+        loop {
+            table.close();
+            let hypothesis = table.hypothesis();
+            equivalence_queries += 1;
+
+            match oracle.find_counterexample(&hypothesis) {
+                Some(counterexample) => table.add_suffixes(&counterexample),
+                None => {
+                    return Learned {
+                        state_machine: hypothesis.minimize(),
+                        membership_queries: table.memberships.len(),
+                        equivalence_queries,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Observation table of the L* algorithm. Each prefix has a row holding the membership of the
+/// prefix followed by each suffix. Prefixes with distinct rows stand for distinct states.
+struct ObservationTable<'a, O: ?Sized> {
+    /// Oracle answering membership queries.
+    oracle: &'a O,
+    /// Symbols of the language.
+    alphabet: Vec<String>,
+    /// Access words of the states, all with distinct rows.
+    prefixes: Vec<Vec<String>>,
+    /// Distinguishing suffixes, starting with the empty word.
+    suffixes: Vec<Vec<String>>,
+    /// Answers to the membership queries asked so far.
+    memberships: HashMap<Vec<String>, bool>,
+}
+
+impl<'a, O> ObservationTable<'a, O>
+where
+    O: Oracle + ?Sized,
+{
+    /// Creates a table with the empty word as its only prefix and suffix.
+    fn new(oracle: &'a O) -> Self {
+        Self {
+            oracle,
+            alphabet: oracle.alphabet(),
+            prefixes: vec![Vec::new()],
+            suffixes: vec![Vec::new()],
+            memberships: HashMap::new(),
+        }
+    }
+
+    /// Returns the row of `prefix`, querying the oracle for missing entries.
+    fn row(&mut self, prefix: &[String]) -> Vec<bool> {
+        let oracle = self.oracle;
+
+        self.suffixes
+            .iter()
+            .map(|suffix| {
+                let word = [prefix, suffix].concat();
+                *self.memberships.entry(word).or_insert_with_key(|word| oracle.is_member(word))
+            })
+            .collect()
+    }
+
+    /// Adds prefixes until the row of every prefix followed by a symbol is the row of a prefix.
+    fn close(&mut self) {
+        let prefixes = self.prefixes.clone();
+        let mut rows = prefixes.iter().map(|prefix| self.row(prefix)).collect::<HashSet<_>>();
+        let mut i = 0;
+
+        while i < self.prefixes.len() {
+            let prefix = self.prefixes[i].clone();
+
+            for symbol in self.alphabet.clone() {
+                let word = [prefix.as_slice(), &[symbol]].concat();
+
+                if rows.insert(self.row(&word)) {
+                    self.prefixes.push(word);
+                }
+            }
+
+            i += 1;
+        }
+    }
+
+    /// Returns the state machine described by a closed table, with a state `q{i}` for the
+    /// `i`-th prefix.
+    fn hypothesis(&mut self) -> StateMachine {
+        let prefixes = self.prefixes.clone();
+        let rows = prefixes
+            .iter()
+            .enumerate()
+            .map(|(i, prefix)| (self.row(prefix), format!("q{}", i)))
+            .collect::<HashMap<_, _>>();
+
+        let mut machine = SerializedStateMachine {
+            states: rows.values().cloned().collect(),
+            alphabet: self.alphabet.iter().cloned().collect(),
+            transitions: BTreeSet::new(),
+            initial_state: "q0".to_owned(),
+            final_states: BTreeSet::new(),
+            tags: BTreeMap::new(),
+        };
+
+        for (i, prefix) in prefixes.iter().enumerate() {
+            let name = format!("q{}", i);
+
+            // The first suffix is the empty word, so the first entry tells whether the prefix
+            // itself is accepted.
+            if self.row(prefix)[0] {
+                machine.final_states.insert(name.clone());
+            }
+
+            for symbol in self.alphabet.clone() {
+                let word = [prefix.as_slice(), std::slice::from_ref(&symbol)].concat();
+
+                machine.transitions.insert(SerializedTrans {
+                    src_state: name.clone(),
+                    symbol,
+                    dst_state: rows[&self.row(&word)].clone(),
+                });
+            }
+        }
+
+        StateMachine::try_from(machine).expect("Hypothesis state machine is invalid")
+    }
+
+    /// Adds every suffix of `counterexample` that is not a suffix of the table yet.
+    fn add_suffixes(&mut self, counterexample: &[String]) {
+        for i in 0..counterexample.len() {
+            let suffix = counterexample[i..].to_vec();
+
+            if !self.suffixes.contains(&suffix) {
+                self.suffixes.push(suffix);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::{fixtures, parse_test_cases};
+
+    fn regexp(pattern: &str) -> StateMachine {
+        StateMachine::from_regexp(pattern).unwrap()
+    }
+
+    #[test]
+    fn test_learn_from_state_machine() {
+        for target in [fixtures::number(), fixtures::identifier()] {
+            let learned = StateMachine::learn(&target).state_machine;
+
+            assert_eq!(learned.equivalent(&target), Ok(()));
+            assert_eq!(learned.iter_states().count(), target.minimize().iter_states().count());
+        }
+    }
+
+    #[test]
+    fn test_learn_with_counterexamples() {
+        // The third symbol from the end is an `a`, which needs several counterexamples.
+        let target = regexp("(a|b)*a(a|b)(a|b)");
+        let learned = StateMachine::learn(&target);
+
+        assert_eq!(learned.state_machine.equivalent(&target), Ok(()));
+        assert_eq!(learned.state_machine.iter_states().count(), 8);
+        assert!(learned.equivalence_queries > 1);
+    }
+
+    #[test]
+    fn test_learn_from_examples() {
+        let cases =
+            parse_test_cases(include_str!("../../../state-machines/tests/number.txt")).unwrap();
+        let oracle = ExampleOracle::new(&cases).unwrap();
+        let learned = StateMachine::learn(&oracle).state_machine;
+
+        assert!(learned.run_test_cases(&cases).is_success());
+        assert_eq!(oracle.find_counterexample(&learned), None);
+
+        let conflicting = parse_test_cases("accept ab\nreject b\nreject ab\n").unwrap();
+        assert!(ExampleOracle::new(&conflicting).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use crate::state_machine::StateMachine;
+use std::ops::Range;
+
+impl StateMachine {
+    /// Returns the length of the longest prefix of `sequence` accepted by the state machine.
+    pub fn longest_accepted_prefix<S>(&self, sequence: &[S]) -> Option<usize>
+    where
+        S: AsRef<str>,
+    {
+        self.all_accepted_prefixes(sequence).pop()
+    }
+
+    /// Returns the lengths of all prefixes of `sequence` accepted by the state machine, in
+    /// increasing order.
+    pub fn all_accepted_prefixes<S>(&self, sequence: &[S]) -> Vec<usize>
+    where
+        S: AsRef<str>,
+    {
+        let mut prefixes = Vec::new();
+        let mut state: &str = &self.initial_state;
+
+        if self.final_states.contains(state) {
+            prefixes.push(0);
+        }
+
+        for (i, symbol) in sequence.iter().enumerate() {
+            match self.next_state(state, symbol.as_ref()) {
+                Some(next_state) => state = next_state,
+                None => break,
+            }
+
+            if self.final_states.contains(state) {
+                prefixes.push(i + 1);
+            }
+        }
+
+        prefixes
+    }
+
+    /// Returns the ranges of all non-empty subsequences of `sequence` accepted by the state
+    /// machine. Matches don't overlap: the scan starts at the beginning of `sequence` and always
+    /// takes the longest match before resuming after it.
+    pub fn find_matches<S>(&self, sequence: &[S]) -> Vec<Range<usize>>
+    where
+        S: AsRef<str>,
+    {
+        let mut matches = Vec::new();
+        let mut start = 0;
+
+        while start < sequence.len() {
+            match self.longest_accepted_prefix(&sequence[start..]) {
+                Some(len) if len != 0 => {
+                    matches.push(start..(start + len));
+                    start += len;
+                }
+                _ => start += 1,
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state_machine::fixtures;
+    use crate::utils;
+
+    #[test]
+    fn test_accepted_prefixes() {
+        let state_machine = fixtures::number();
+
+        let sequence = utils::str_to_grapheme_clusters("120+");
+        assert_eq!(state_machine.all_accepted_prefixes(&sequence), vec![1, 2, 3]);
+        assert_eq!(state_machine.longest_accepted_prefix(&sequence), Some(3));
+
+        let sequence = utils::str_to_grapheme_clusters("012");
+        assert!(state_machine.all_accepted_prefixes(&sequence).is_empty());
+        assert_eq!(state_machine.longest_accepted_prefix(&sequence), None);
+    }
+
+    #[test]
+    fn test_find_matches() {
+        let state_machine = fixtures::identifier();
+
+        let sequence = utils::str_to_grapheme_clusters("a1+_+__a 1a");
+        assert_eq!(state_machine.find_matches(&sequence), vec![0..2, 5..8, 10..11]);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::state_machine::{SerializedStateMachine, SerializedTrans, StateMachine, Tag};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+impl StateMachine {
+    /// Returns the minimal state machine accepting the same language.
+    ///
+    /// Unreachable and dead states are removed and equivalent states are merged using partition
+    /// refinement. Final states with different tags are never merged. The remaining states are
+    /// named `q0`, `q1`, ... in breadth-first order, with symbols visited alphabetically.
+    pub fn minimize(&self) -> StateMachine {
+        let symbols = self.sorted_symbols();
+        let reachable = self.reachable_states();
+        let productive = self.productive_states();
+
+        let mut states = self
+            .sorted_states()
+            .into_iter()
+            .filter(|state| reachable.contains(state) && productive.contains(state))
+            .collect::<Vec<_>>();
+
+        let useful = states.iter().copied().collect::<HashSet<_>>();
+
+        // The language is empty, so only the initial state is kept, without any transitions.
+        if states.is_empty() {
+            states.push(self.initial_state());
+        }
+
+        let next_state = |state: &str, symbol: &str| {
+            self.next_state(state, symbol).filter(|next_state| useful.contains(next_state))
+        };
+
+        // Start by splitting the states into non-final ones and final ones with each tag, then
+        // split each class by the classes its transitions lead to until no class can be split
+        // further.
+        let mut initial_classes = HashMap::<(bool, Option<&Tag>), usize>::new();
+        let mut classes = states
+            .iter()
+            .map(|&state| {
+                let key = (self.final_states.contains(state), self.tag(state));
+                let class_count = initial_classes.len();
+                (state, *initial_classes.entry(key).or_insert(class_count))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut class_count = classes.values().collect::<HashSet<_>>().len();
+
+        loop {
+            let mut signatures = HashMap::<(usize, Vec<Option<usize>>), usize>::new();
+
+            let next_classes = states
+                .iter()
+                .map(|&state| {
+                    let targets = symbols
+                        .iter()
+                        .map(|symbol| next_state(state, symbol).map(|next| classes[next]))
+                        .collect::<Vec<_>>();
+
+                    let signature_count = signatures.len();
+                    let class =
+                        *signatures.entry((classes[state], targets)).or_insert(signature_count);
+
+                    (state, class)
+                })
+                .collect::<HashMap<_, _>>();
+
+            classes = next_classes;
+
+            if signatures.len() == class_count {
+                break;
+            }
+
+            class_count = signatures.len();
+        }
+
+        // The first state of each class, in sorted order, represents the whole class.
+        let mut representatives = HashMap::<usize, &str>::new();
+        for &state in states.iter() {
+            representatives.entry(classes[state]).or_insert(state);
+        }
+
+        let initial_class = classes[self.initial_state()];
+        let mut names = HashMap::<usize, String>::from([(initial_class, "q0".to_owned())]);
+        let mut queue = VecDeque::from([initial_class]);
+
+        let mut machine = SerializedStateMachine {
+            states: BTreeSet::new(),
+            alphabet: symbols.iter().map(|&symbol| symbol.to_owned()).collect(),
+            transitions: BTreeSet::new(),
+            initial_state: "q0".to_owned(),
+            final_states: BTreeSet::new(),
+            tags: BTreeMap::new(),
+        };
+
+        while let Some(class) = queue.pop_front() {
+            let state = representatives[&class];
+            let name = names[&class].clone();
+
+            if self.final_states.contains(state) {
+                machine.final_states.insert(name.clone());
+            }
+
+            if let Some(tag) = self.tag(state) {
+                machine.tags.insert(name.clone(), tag.clone());
+            }
+
+            for &symbol in symbols.iter() {
+                if let Some(next_state) = next_state(state, symbol) {
+                    let next_class = classes[next_state];
+
+                    let next_name = match names.get(&next_class) {
+                        Some(next_name) => next_name.clone(),
+                        None => {
+                            let next_name = format!("q{}", names.len());
+                            names.insert(next_class, next_name.clone());
+                            queue.push_back(next_class);
+                            next_name
+                        }
+                    };
+
+                    machine.transitions.insert(SerializedTrans {
+                        src_state: name.clone(),
+                        symbol: symbol.to_owned(),
+                        dst_state: next_name,
+                    });
+                }
+            }
+
+            machine.states.insert(name);
+        }
+
+        StateMachine::try_from(machine).expect("Minimized state machine is invalid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimize() {
+        // Accepts identifiers, with the final state split in two and an unreachable state.
+        let state_machine = StateMachine::from_fa_str(
+            "s u f g x\n_ a 1\ns\nf g\n\
+             s _ u\ns a f\nu _ f\nu a f\nu 1 f\nf _ g\nf a g\nf 1 g\ng _ f\ng a f\ng 1 f\n\
+             x a s\n",
+        )
+        .unwrap();
+
+        let minimized = state_machine.minimize();
+        assert_eq!(minimized.iter_states().count(), 3);
+        assert_eq!(minimized.initial_state(), "q0");
+        assert_eq!(minimized.equivalent(&state_machine), Ok(()));
+    }
+
+    #[test]
+    fn test_minimize_removes_dead_states() {
+        let state_machine = StateMachine::from_fa_str("a b c\n0 1\na\nb\na 0 b\na 1 c\nc 0 c\n")
+            .unwrap()
+            .minimize();
+
+        assert_eq!(state_machine.to_fa_string(), "q0 q1\n0 1\nq0\nq1\nq0 0 q1\n");
+
+        let empty = StateMachine::from_fa_str("a b\n0\na\nb\na 0 a\n").unwrap().minimize();
+        assert_eq!(empty.to_fa_string(), "q0\n0\nq0\n\n");
+    }
+}
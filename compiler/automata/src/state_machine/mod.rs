@@ -0,0 +1,352 @@
+/// Reachability, productivity and completeness analysis.
+mod analysis;
+
+/// Batch acceptance tests read from cases files.
+mod cases;
+
+/// Generation of Rust modules running state machines.
+mod codegen;
+
+/// Dense table form of state machines.
+mod compiled;
+
+/// Graphviz DOT export.
+mod dot;
+
+/// Adding states, symbols and transitions to existing state machines.
+mod edit;
+
+/// Conversion of state machines into regular expressions.
+mod elimination;
+
+/// Enumeration, counting and random sampling of accepted sequences.
+mod enumeration;
+
+/// Equivalence and inclusion checks.
+mod equivalence;
+
+/// Reading and writing state machines in the supported file formats.
+mod format;
+
+/// Concatenation, union and Kleene operations producing nondeterministic state machines.
+mod kleene;
+
+/// Angluin's L* algorithm, learning state machines from membership and equivalence queries.
+mod learning;
+
+/// Longest-prefix and substring matching.
+mod matching;
+
+/// State machine minimization.
+mod minimize;
+
+/// Nondeterministic state machines and the subset construction.
+mod nfa;
+
+/// Boolean operations on state machines.
+mod ops;
+
+/// Pushdown automata and their simulation.
+mod pushdown;
+
+/// Regular expression parsing and compilation into state machines.
+mod regexp;
+
+/// Regular grammars in BNF notation and their conversion to and from state machines.
+mod regular_grammar;
+
+/// Incremental, symbol-by-symbol runs of state machines.
+mod runner;
+
+/// Transition tables for displaying state machines.
+mod table;
+
+/// Tags classifying the sequences accepted by final states.
+mod tags;
+
+/// Step-by-step tracing of state machine runs.
+mod trace;
+
+/// Moore and Mealy transducers.
+mod transducer;
+
+pub use self::analysis::*;
+pub use self::cases::*;
+pub use self::compiled::*;
+pub use self::equivalence::*;
+pub use self::format::*;
+pub use self::learning::*;
+pub use self::nfa::*;
+pub use self::pushdown::*;
+pub use self::regexp::*;
+pub use self::regular_grammar::*;
+pub use self::runner::*;
+pub use self::tags::*;
+pub use self::trace::*;
+pub use self::transducer::*;
+
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+type State = String;
+type Symbol = String;
+type StateArc = Arc<State>;
+type SymbolArc = Arc<String>;
+
+/// Implements the logic required for a finite state machine.
+#[derive(Deserialize)]
+#[serde(try_from = "SerializedStateMachine")]
+pub struct StateMachine {
+    /// All possible states.
+    states: HashSet<StateArc>,
+    /// All symbols that make up the state machine alphabet.
+    alphabet: HashSet<SymbolArc>,
+    /// All transitions supported by the state machine.
+    transitions: HashMap<State, HashMap<Symbol, StateArc>>,
+    /// The initial state of the state machie.
+    initial_state: StateArc,
+    /// The final state of the state machine.
+    final_states: HashSet<State>,
+    /// Tags of the final states. Final states without a tag accept sequences without
+    /// classifying them.
+    tags: HashMap<State, Tag>,
+}
+
+impl StateMachine {
+    /// Returns whether a sequence is accepted by the state machine.
+    pub fn is_accepted<S>(&self, sequence: &[S]) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let mut runner = self.runner();
+
+        for symbol in sequence.iter() {
+            if !runner.feed(symbol.as_ref()) {
+                return false;
+            }
+        }
+
+        runner.is_accepting()
+    }
+
+    /// Returns the state reached from `state` on `symbol`, if such a transition exists.
+    pub fn next_state(&self, state: &str, symbol: &str) -> Option<&str> {
+        self.transitions
+            .get(state)
+            .and_then(|transitions| transitions.get(symbol))
+            .map(|state| state.as_str())
+    }
+
+    /// Returns all states, sorted alphabetically.
+    pub fn sorted_states(&self) -> Vec<&str> {
+        let mut states = self.iter_states().collect::<Vec<_>>();
+        states.sort_unstable();
+        states
+    }
+
+    /// Returns all symbols, sorted alphabetically.
+    pub fn sorted_symbols(&self) -> Vec<&str> {
+        let mut symbols = self.iter_symbols().collect::<Vec<_>>();
+        symbols.sort_unstable();
+        symbols
+    }
+
+    /// Returns an iterator over all states.
+    pub fn iter_states(&self) -> impl Iterator<Item = &str> {
+        self.states.iter().map(|state| state.as_str())
+    }
+
+    /// Returns an iterator over all symbols.
+    pub fn iter_symbols(&self) -> impl Iterator<Item = &str> {
+        self.alphabet.iter().map(|state| state.as_str())
+    }
+
+    /// Returns an iterator over all transitions, where each item is a tuple of the shape
+    /// `(src_state, symbol, dst_state)`.
+    pub fn iter_transitions(&self) -> impl Iterator<Item = (&str, &str, &str)> {
+        self.transitions.iter().flat_map(|(src_state, transitions)| {
+            transitions.iter().map(|(symbol, dst_state)| {
+                (src_state.as_str(), symbol.as_str(), dst_state.as_str())
+            })
+        })
+    }
+
+    /// Returns the initial state of the state machine.
+    pub fn initial_state(&self) -> &str {
+        self.initial_state.as_str()
+    }
+
+    /// Returns the final state of the state machine.
+    pub fn final_states(&self) -> impl Iterator<Item = &str> {
+        self.final_states.iter().map(|state| state.as_str())
+    }
+}
+
+impl TryFrom<SerializedStateMachine> for StateMachine {
+    type Error = StateMachineDeserError;
+
+    fn try_from(machine: SerializedStateMachine) -> Result<Self, Self::Error> {
+        let states = machine.states.into_iter().map(Arc::new).collect::<HashSet<_>>();
+
+        let symbols = machine.alphabet.into_iter().map(Arc::new).collect::<HashSet<_>>();
+
+        let mut transitions = HashMap::<State, HashMap<Symbol, StateArc>>::new();
+
+        for transition in machine.transitions {
+            let src_state = states
+                .get(&transition.src_state)
+                .ok_or(StateMachineDeserError::InvalidState(transition.src_state))?;
+
+            let symbol = symbols
+                .get(&transition.symbol)
+                .ok_or(StateMachineDeserError::InvalidSymbol(transition.symbol))?;
+
+            let dst_state = states
+                .get(&transition.dst_state)
+                .cloned()
+                .ok_or(StateMachineDeserError::InvalidState(transition.dst_state))?;
+
+            transitions
+                .entry(src_state.as_str().to_owned())
+                .or_default()
+                .insert(symbol.as_str().to_owned(), dst_state.clone());
+        }
+
+        let initial_state = states
+            .get(&machine.initial_state)
+            .cloned()
+            .ok_or(StateMachineDeserError::InvalidState(machine.initial_state))?;
+
+        let mut final_states = HashSet::<State>::new();
+        for state in machine.final_states {
+            match states.get(&state) {
+                Some(state) => {
+                    final_states.insert(state.as_str().to_owned());
+                }
+                None => return Err(StateMachineDeserError::InvalidState(state)),
+            }
+        }
+
+        let mut tags = HashMap::<State, Tag>::new();
+        for (state, tag) in machine.tags {
+            if !final_states.contains(&state) {
+                return Err(StateMachineDeserError::NonFinalTag(state));
+            }
+
+            tags.insert(state, tag);
+        }
+
+        Ok(StateMachine {
+            states,
+            alphabet: symbols,
+            transitions,
+            initial_state,
+            final_states,
+            tags,
+        })
+    }
+}
+
+impl From<&StateMachine> for SerializedStateMachine {
+    fn from(machine: &StateMachine) -> Self {
+        let transitions = machine
+            .iter_transitions()
+            .map(|(src_state, symbol, dst_state)| SerializedTrans {
+                src_state: src_state.to_owned(),
+                symbol: symbol.to_owned(),
+                dst_state: dst_state.to_owned(),
+            })
+            .collect();
+
+        Self {
+            states: machine.iter_states().map(str::to_owned).collect(),
+            alphabet: machine.iter_symbols().map(str::to_owned).collect(),
+            transitions,
+            initial_state: machine.initial_state().to_owned(),
+            final_states: machine.final_states().map(str::to_owned).collect(),
+            tags: machine.tags.iter().map(|(state, tag)| (state.clone(), tag.clone())).collect(),
+        }
+    }
+}
+
+impl Serialize for StateMachine {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializedStateMachine::from(self).serialize(serializer)
+    }
+}
+
+/// Errors the may be returned when deserializing a state machine.
+#[derive(Clone, Debug)]
+pub enum StateMachineDeserError {
+    /// The given state is invalid.
+    InvalidState(String),
+    /// The given symbol is invalid.
+    InvalidSymbol(String),
+    /// A required line is missing from the `FA.in` file.
+    MissingLine(&'static str),
+    /// The given line of the `FA.in` file must hold a single token, but holds the given number
+    /// of tokens.
+    NotSingleToken(&'static str, usize),
+    /// The transition on the given line is malformed.
+    InvalidTransition(usize),
+    /// The given state has a tag, but is not final.
+    NonFinalTag(String),
+}
+
+impl Error for StateMachineDeserError {}
+
+impl fmt::Display for StateMachineDeserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidState(state) => write!(f, "Invalid state \"{}\"", state),
+            Self::InvalidSymbol(symbol) => write!(f, "Invalid symbol \"{}\"", symbol),
+            Self::MissingLine(line) => write!(f, "Missing {} line", line),
+            Self::NotSingleToken(line, count) => {
+                write!(f, "Expected a single token on the {} line, found {}", line, count)
+            }
+            Self::InvalidTransition(line) => write!(f, "Invalid transition on line {}", line),
+            Self::NonFinalTag(state) => write!(f, "Tagged state \"{}\" is not final", state),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize, Serialize)]
+struct SerializedTrans {
+    src_state: String,
+    symbol: String,
+    dst_state: String,
+}
+
+/// Serialized form of a state machine. Uses ordered sets so the output is stable across runs.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct SerializedStateMachine {
+    states: BTreeSet<String>,
+    alphabet: BTreeSet<String>,
+    transitions: BTreeSet<SerializedTrans>,
+    initial_state: String,
+    final_states: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    tags: BTreeMap<String, Tag>,
+}
+
+/// State machines of `state-machines/` shared by the tests.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::state_machine::StateMachine;
+
+    /// Returns the state machine of `state-machines/number.json`.
+    pub fn number() -> StateMachine {
+        serde_json::from_str(include_str!("../../../state-machines/number.json")).unwrap()
+    }
+
+    /// Returns the state machine of `state-machines/identifier.json`.
+    pub fn identifier() -> StateMachine {
+        serde_json::from_str(include_str!("../../../state-machines/identifier.json")).unwrap()
+    }
+}
@@ -0,0 +1,270 @@
+use crate::state_machine::{SerializedStateMachine, SerializedTrans, StateMachine, Tag};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// Nondeterministic finite state machine with epsilon transitions. States are identified by
+/// their index.
+#[derive(Clone, Debug)]
+pub struct Nfa {
+    /// Transitions leaving each state. Transitions on [None] are epsilon transitions.
+    transitions: Vec<Vec<(Option<String>, usize)>>,
+    /// The initial state.
+    initial_state: usize,
+    /// The final states.
+    final_states: BTreeSet<usize>,
+    /// Tags of the final states. Tags of states that are not final are ignored.
+    tags: BTreeMap<usize, Tag>,
+}
+
+impl Default for Nfa {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Nfa {
+    /// Creates a state machine with a single, non-final initial state and no transitions.
+    pub fn new() -> Self {
+        Self {
+            transitions: vec![Vec::new()],
+            initial_state: 0,
+            final_states: BTreeSet::new(),
+            tags: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a new state and returns its index.
+    pub fn add_state(&mut self) -> usize {
+        self.transitions.push(Vec::new());
+        self.transitions.len() - 1
+    }
+
+    /// Adds a transition from `src_state` to `dst_state` on `symbol`, or an epsilon transition
+    /// if `symbol` is [None].
+    pub fn add_transition(&mut self, src_state: usize, symbol: Option<&str>, dst_state: usize) {
+        self.transitions[src_state].push((symbol.map(str::to_owned), dst_state));
+    }
+
+    /// Sets the initial state.
+    pub fn set_initial_state(&mut self, state: usize) {
+        self.initial_state = state;
+    }
+
+    /// Marks `state` as final or non-final.
+    pub fn set_final(&mut self, state: usize, is_final: bool) {
+        if is_final {
+            self.final_states.insert(state);
+        } else {
+            self.final_states.remove(&state);
+        }
+    }
+
+    /// Sets or removes the tag of `state`. The tag only applies while the state is final.
+    pub fn set_tag(&mut self, state: usize, tag: Option<Tag>) {
+        match tag {
+            Some(tag) => self.tags.insert(state, tag),
+            None => self.tags.remove(&state),
+        };
+    }
+
+    /// Returns the tag of `state`, if it is a tagged final state.
+    pub fn tag(&self, state: usize) -> Option<&Tag> {
+        self.tags.get(&state).filter(|_| self.final_states.contains(&state))
+    }
+
+    /// Returns the number of states.
+    pub fn state_count(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns the initial state.
+    pub fn initial_state(&self) -> usize {
+        self.initial_state
+    }
+
+    /// Returns an iterator over the final states.
+    pub fn final_states(&self) -> impl Iterator<Item = usize> + '_ {
+        self.final_states.iter().copied()
+    }
+
+    /// Returns an iterator over all transitions, where each item is a tuple of the shape
+    /// `(src_state, symbol, dst_state)`. Epsilon transitions have no symbol.
+    pub fn iter_transitions(&self) -> impl Iterator<Item = (usize, Option<&str>, usize)> {
+        self.transitions.iter().enumerate().flat_map(|(src_state, transitions)| {
+            transitions
+                .iter()
+                .map(move |(symbol, dst_state)| (src_state, symbol.as_deref(), *dst_state))
+        })
+    }
+
+    /// Returns all symbols used by the transitions, sorted alphabetically.
+    pub fn alphabet(&self) -> BTreeSet<&str> {
+        self.iter_transitions().filter_map(|(_, symbol, _)| symbol).collect()
+    }
+
+    /// Returns all states reachable from `states` using only epsilon transitions, including
+    /// `states` themselves.
+    pub fn epsilon_closure(&self, states: &BTreeSet<usize>) -> BTreeSet<usize> {
+        let mut closure = states.clone();
+        let mut stack = states.iter().copied().collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            for (symbol, dst_state) in self.transitions[state].iter() {
+                if symbol.is_none() && closure.insert(*dst_state) {
+                    stack.push(*dst_state);
+                }
+            }
+        }
+
+        closure
+    }
+
+    /// Returns the epsilon closure of all states reachable from `states` on `symbol`.
+    pub fn step(&self, states: &BTreeSet<usize>, symbol: &str) -> BTreeSet<usize> {
+        let next_states = states
+            .iter()
+            .flat_map(|&state| self.transitions[state].iter())
+            .filter(|(transition_symbol, _)| transition_symbol.as_deref() == Some(symbol))
+            .map(|(_, dst_state)| *dst_state)
+            .collect();
+
+        self.epsilon_closure(&next_states)
+    }
+
+    /// Returns whether a sequence is accepted by the state machine.
+    pub fn is_accepted<S>(&self, sequence: &[S]) -> bool
+    where
+        S: AsRef<str>,
+    {
+        let mut states = self.epsilon_closure(&BTreeSet::from([self.initial_state]));
+
+        for symbol in sequence.iter() {
+            states = self.step(&states, symbol.as_ref());
+        }
+
+        states.iter().any(|state| self.final_states.contains(state))
+    }
+
+    /// Converts the state machine into a deterministic [StateMachine] using the subset
+    /// construction. Only reachable, non-empty subsets become states, which are named `q0`,
+    /// `q1`, ... in breadth-first order. Each subset gets the tag with the highest priority among
+    /// its final states.
+    pub fn to_state_machine(&self) -> StateMachine {
+        let alphabet = self.alphabet();
+
+        let initial_subset = self.epsilon_closure(&BTreeSet::from([self.initial_state]));
+        let mut names = HashMap::<BTreeSet<usize>, String>::new();
+        let mut queue = VecDeque::new();
+
+        names.insert(initial_subset.clone(), "q0".to_owned());
+        queue.push_back(initial_subset);
+
+        let mut machine = SerializedStateMachine {
+            states: BTreeSet::new(),
+            alphabet: alphabet.iter().map(|&symbol| symbol.to_owned()).collect(),
+            transitions: BTreeSet::new(),
+            initial_state: "q0".to_owned(),
+            final_states: BTreeSet::new(),
+            tags: BTreeMap::new(),
+        };
+
+        while let Some(subset) = queue.pop_front() {
+            let name = names[&subset].clone();
+
+            if subset.iter().any(|state| self.final_states.contains(state)) {
+                machine.final_states.insert(name.clone());
+            }
+
+            if let Some(tag) = Tag::highest(subset.iter().filter_map(|&state| self.tag(state))) {
+                machine.tags.insert(name.clone(), tag.clone());
+            }
+
+            for &symbol in alphabet.iter() {
+                let next_subset = self.step(&subset, symbol);
+
+                if next_subset.is_empty() {
+                    continue;
+                }
+
+                let next_name = match names.get(&next_subset) {
+                    Some(next_name) => next_name.clone(),
+                    None => {
+                        let next_name = format!("q{}", names.len());
+                        names.insert(next_subset.clone(), next_name.clone());
+                        queue.push_back(next_subset);
+                        next_name
+                    }
+                };
+
+                machine.transitions.insert(SerializedTrans {
+                    src_state: name.clone(),
+                    symbol: symbol.to_owned(),
+                    dst_state: next_name,
+                });
+            }
+
+            machine.states.insert(name);
+        }
+
+        StateMachine::try_from(machine).expect("Determinized state machine is invalid")
+    }
+}
+
+impl From<&StateMachine> for Nfa {
+    fn from(state_machine: &StateMachine) -> Self {
+        let states = state_machine.sorted_states();
+        let indices =
+            states.iter().enumerate().map(|(i, &state)| (state, i)).collect::<HashMap<_, _>>();
+
+        let mut nfa = Nfa {
+            transitions: vec![Vec::new(); states.len()],
+            initial_state: indices[state_machine.initial_state()],
+            final_states: state_machine.final_states().map(|state| indices[state]).collect(),
+            tags: state_machine
+                .iter_tags()
+                .map(|(state, tag)| (indices[state], tag.clone()))
+                .collect(),
+        };
+
+        for (src_state, symbol, dst_state) in state_machine.iter_transitions() {
+            nfa.add_transition(indices[src_state], Some(symbol), indices[dst_state]);
+        }
+
+        nfa
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subset_construction() {
+        // Accepts sequences over {a, b} ending in "ab".
+        let mut nfa = Nfa::new();
+        let s1 = nfa.add_state();
+        let s2 = nfa.add_state();
+        let s3 = nfa.add_state();
+
+        nfa.add_transition(0, Some("a"), 0);
+        nfa.add_transition(0, Some("b"), 0);
+        nfa.add_transition(0, None, s1);
+        nfa.add_transition(s1, Some("a"), s2);
+        nfa.add_transition(s2, Some("b"), s3);
+        nfa.set_final(s3, true);
+
+        let state_machine = nfa.to_state_machine();
+
+        for sequence in [&["a", "b"][..], &["b", "a", "a", "b"], &["a", "b", "a", "b"]] {
+            assert!(nfa.is_accepted(sequence));
+            assert!(state_machine.is_accepted(sequence));
+        }
+
+        for sequence in [&[][..], &["a"], &["b", "a"], &["a", "b", "a"]] {
+            assert!(!nfa.is_accepted(sequence));
+            assert!(!state_machine.is_accepted(sequence));
+        }
+
+        assert_eq!(state_machine.initial_state(), "q0");
+        assert_eq!(state_machine.iter_states().count(), 3);
+    }
+}
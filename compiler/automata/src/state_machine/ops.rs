@@ -0,0 +1,236 @@
+use crate::state_machine::{SerializedStateMachine, SerializedTrans, StateMachine, Tag};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// State of a product state machine. [None] stands for the implicit sink state of a machine
+/// whose transition function is not defined for a symbol.
+type ProductState<'a> = (Option<&'a str>, Option<&'a str>);
+
+impl StateMachine {
+    /// Returns a state machine accepting the sequences accepted by `self` or `other`.
+    pub fn union(&self, other: &StateMachine) -> StateMachine {
+        self.product(other, |accepted, other_accepted| accepted || other_accepted)
+    }
+
+    /// Returns a state machine accepting the sequences accepted by both `self` and `other`.
+    pub fn intersection(&self, other: &StateMachine) -> StateMachine {
+        self.product(other, |accepted, other_accepted| accepted && other_accepted)
+    }
+
+    /// Returns a state machine accepting the sequences accepted by `self`, but not by `other`.
+    pub fn difference(&self, other: &StateMachine) -> StateMachine {
+        self.product(other, |accepted, other_accepted| accepted && !other_accepted)
+    }
+
+    /// Returns a state machine accepting the sequences accepted by exactly one of `self` and
+    /// `other`.
+    pub fn symmetric_difference(&self, other: &StateMachine) -> StateMachine {
+        self.product(other, |accepted, other_accepted| accepted != other_accepted)
+    }
+
+    /// Returns a state machine whose transition function is defined for every state and every
+    /// symbol of its alphabet, extended with `alphabet`. Missing transitions lead to a new sink
+    /// state.
+    pub fn complete<S>(&self, alphabet: &[S]) -> StateMachine
+    where
+        S: AsRef<str>,
+    {
+        let mut machine = SerializedStateMachine::from(self);
+        machine.alphabet.extend(alphabet.iter().map(|symbol| symbol.as_ref().to_owned()));
+
+        let mut sink = String::from("sink");
+        while machine.states.contains(&sink) {
+            sink.push('\'');
+        }
+
+        let mut missing_transitions = Vec::new();
+        for state in machine.states.iter() {
+            for symbol in machine.alphabet.iter() {
+                if self.next_state(state, symbol).is_none() {
+                    missing_transitions.push(SerializedTrans {
+                        src_state: state.clone(),
+                        symbol: symbol.clone(),
+                        dst_state: sink.clone(),
+                    });
+                }
+            }
+        }
+
+        if !missing_transitions.is_empty() {
+            for symbol in machine.alphabet.iter() {
+                missing_transitions.push(SerializedTrans {
+                    src_state: sink.clone(),
+                    symbol: symbol.clone(),
+                    dst_state: sink.clone(),
+                });
+            }
+
+            machine.states.insert(sink);
+            machine.transitions.extend(missing_transitions);
+        }
+
+        StateMachine::try_from(machine).expect("Completed state machine is invalid")
+    }
+
+    /// Returns a state machine accepting exactly the sequences over its alphabet, extended with
+    /// `alphabet`, that are not accepted by `self`.
+    pub fn complement<S>(&self, alphabet: &[S]) -> StateMachine
+    where
+        S: AsRef<str>,
+    {
+        let mut machine = SerializedStateMachine::from(&self.complete(alphabet));
+        machine.final_states = machine.states.difference(&machine.final_states).cloned().collect();
+        machine.tags.clear();
+
+        StateMachine::try_from(machine).expect("Complemented state machine is invalid")
+    }
+
+    /// Builds the reachable part of the product of `self` and `other` over the union of their
+    /// alphabets. A product state is final if `accepts` returns `true` when given whether each
+    /// of its components is final, and gets the tag with the highest priority among its final
+    /// components. States are named `(state, other_state)`.
+    fn product<F>(&self, other: &StateMachine, accepts: F) -> StateMachine
+    where
+        F: Fn(bool, bool) -> bool,
+    {
+        let alphabet = self.iter_symbols().chain(other.iter_symbols()).collect::<BTreeSet<_>>();
+
+        let mut names = HashMap::<ProductState, String>::new();
+        let mut used_names = HashSet::<String>::new();
+
+        let mut name_of = |product_state| -> String {
+            names
+                .entry(product_state)
+                .or_insert_with(|| {
+                    let (state, other_state) = product_state;
+                    let mut name = format!(
+                        "({}, {})",
+                        state.unwrap_or("sink"),
+                        other_state.unwrap_or("sink"),
+                    );
+
+                    while !used_names.insert(name.clone()) {
+                        name.push('\'');
+                    }
+
+                    name
+                })
+                .clone()
+        };
+
+        let initial = (Some(self.initial_state()), Some(other.initial_state()));
+        let mut visited = HashSet::<ProductState>::from([initial]);
+        let mut queue = VecDeque::from([initial]);
+
+        let mut machine = SerializedStateMachine {
+            states: BTreeSet::new(),
+            alphabet: alphabet.iter().map(|&symbol| symbol.to_owned()).collect(),
+            transitions: BTreeSet::new(),
+            initial_state: name_of(initial),
+            final_states: BTreeSet::new(),
+            tags: BTreeMap::new(),
+        };
+
+        while let Some(product_state @ (state, other_state)) = queue.pop_front() {
+            let name = name_of(product_state);
+
+            let is_final = state.is_some_and(|state| self.final_states.contains(state));
+            let other_is_final =
+                other_state.is_some_and(|state| other.final_states.contains(state));
+
+            if accepts(is_final, other_is_final) {
+                machine.final_states.insert(name.clone());
+
+                let tags = [
+                    state.and_then(|state| self.tag(state)),
+                    other_state.and_then(|state| other.tag(state)),
+                ];
+                if let Some(tag) = Tag::highest(tags.into_iter().flatten()) {
+                    machine.tags.insert(name.clone(), tag.clone());
+                }
+            }
+
+            for &symbol in alphabet.iter() {
+                let next_state = state.and_then(|state| self.next_state(state, symbol));
+                let other_next_state =
+                    other_state.and_then(|state| other.next_state(state, symbol));
+
+                // Both machines are stuck, so no sequence can be accepted from here on.
+                if next_state.is_none() && other_next_state.is_none() {
+                    continue;
+                }
+
+                let next_product_state = (next_state, other_next_state);
+                if visited.insert(next_product_state) {
+                    queue.push_back(next_product_state);
+                }
+
+                machine.transitions.insert(SerializedTrans {
+                    src_state: name.clone(),
+                    symbol: symbol.to_owned(),
+                    dst_state: name_of(next_product_state),
+                });
+            }
+
+            machine.states.insert(name);
+        }
+
+        StateMachine::try_from(machine).expect("Product state machine is invalid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+    use crate::utils;
+
+    /// Accepts only `a1`.
+    fn keyword_state_machine() -> StateMachine {
+        StateMachine::from_fa_str("s0 s1 s2\na 1\ns0\ns2\ns0 a s1\ns1 1 s2\n").unwrap()
+    }
+
+    fn accepts(state_machine: &StateMachine, sequence: &str) -> bool {
+        state_machine.is_accepted(&utils::str_to_grapheme_clusters(sequence))
+    }
+
+    #[test]
+    fn test_union() {
+        let union = fixtures::identifier().union(&fixtures::number());
+
+        assert!(accepts(&union, "_a1"));
+        assert!(accepts(&union, "120"));
+        assert!(!accepts(&union, "0"));
+        assert!(!accepts(&union, "1a"));
+        assert_eq!(union.initial_state(), "(initial, initial)");
+    }
+
+    #[test]
+    fn test_intersection() {
+        let intersection = fixtures::identifier().intersection(&keyword_state_machine());
+        assert!(accepts(&intersection, "a1"));
+        assert!(!accepts(&intersection, "a"));
+
+        let intersection = fixtures::identifier().intersection(&fixtures::number());
+        assert!(intersection.is_empty());
+    }
+
+    #[test]
+    fn test_difference() {
+        let difference = fixtures::identifier().difference(&keyword_state_machine());
+
+        assert!(accepts(&difference, "a"));
+        assert!(accepts(&difference, "a11"));
+        assert!(!accepts(&difference, "a1"));
+    }
+
+    #[test]
+    fn test_complement() {
+        let complement = fixtures::number().complement(&["a"]);
+
+        assert!(complement.is_complete());
+        assert!(accepts(&complement, ""));
+        assert!(accepts(&complement, "0"));
+        assert!(accepts(&complement, "1a"));
+        assert!(!accepts(&complement, "10"));
+    }
+}
@@ -0,0 +1,385 @@
+use crate::state_machine::StateMachineDeserError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+/// Condition under which a pushdown automaton accepts its input.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Acceptance {
+    /// The whole input is consumed and the automaton is in a final state.
+    #[default]
+    FinalState,
+    /// The whole input is consumed and the stack is empty.
+    EmptyStack,
+}
+
+/// Transition of a pushdown automaton.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize, Serialize)]
+pub struct PushdownTransition {
+    /// State the transition starts from.
+    pub src_state: String,
+    /// Input symbol consumed by the transition, or [None] for an epsilon transition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Stack symbol that must be on top of the stack and is popped, or [None] to leave the
+    /// stack untouched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pop: Option<String>,
+    /// Stack symbols pushed after popping. The first symbol ends up on top of the stack.
+    #[serde(default)]
+    pub push: Vec<String>,
+    /// State the transition ends in.
+    pub dst_state: String,
+}
+
+/// Nondeterministic pushdown automaton.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(try_from = "SerializedPushdownAutomaton", into = "SerializedPushdownAutomaton")]
+pub struct PushdownAutomaton {
+    /// All states.
+    states: BTreeSet<String>,
+    /// Input symbols.
+    alphabet: BTreeSet<String>,
+    /// Stack symbols.
+    stack_alphabet: BTreeSet<String>,
+    /// Transitions, grouped by source state.
+    transitions: HashMap<String, Vec<PushdownTransition>>,
+    /// The initial state.
+    initial_state: String,
+    /// Contents of the stack before the first step, with the top of the stack first.
+    initial_stack: Vec<String>,
+    /// Final states.
+    final_states: BTreeSet<String>,
+    /// Condition under which the input is accepted.
+    acceptance: Acceptance,
+}
+
+/// State, position in the input and stack contents of a pushdown automaton run.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Configuration {
+    /// The current state.
+    pub state: String,
+    /// Number of input symbols consumed so far.
+    pub position: usize,
+    /// Contents of the stack, with the top of the stack last.
+    pub stack: Vec<String>,
+}
+
+/// Result of running a pushdown automaton on some input.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PushdownOutcome {
+    /// The input is accepted. Holds the configurations of the shortest accepting run.
+    Accepted(Vec<Configuration>),
+    /// Every reachable configuration was explored and none of them accepts the input.
+    Rejected,
+    /// The step bound was reached before finding an accepting configuration.
+    StepLimitReached,
+}
+
+impl PushdownOutcome {
+    /// Returns whether the input was accepted.
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted(_))
+    }
+}
+
+impl PushdownAutomaton {
+    /// Returns the condition under which the input is accepted.
+    pub fn acceptance(&self) -> Acceptance {
+        self.acceptance
+    }
+
+    /// Returns the initial configuration.
+    pub fn initial_configuration(&self) -> Configuration {
+        Configuration {
+            state: self.initial_state.clone(),
+            position: 0,
+            stack: self.initial_stack.iter().rev().cloned().collect(),
+        }
+    }
+
+    /// Returns the configurations reachable in a single step from `configuration`.
+    pub fn successors<S>(&self, configuration: &Configuration, input: &[S]) -> Vec<Configuration>
+    where
+        S: AsRef<str>,
+    {
+        let next_symbol = input.get(configuration.position).map(|symbol| symbol.as_ref());
+        let transitions = self.transitions.get(&configuration.state).into_iter().flatten();
+
+        transitions
+            .filter_map(|transition| {
+                let position = match transition.symbol.as_deref() {
+                    None => configuration.position,
+                    Some(symbol) if Some(symbol) == next_symbol => configuration.position + 1,
+                    Some(_) => return None,
+                };
+
+                let mut stack = configuration.stack.clone();
+                if let Some(pop) = transition.pop.as_ref() {
+                    if stack.pop().as_ref() != Some(pop) {
+                        return None;
+                    }
+                }
+
+                stack.extend(transition.push.iter().rev().cloned());
+
+                Some(Configuration { state: transition.dst_state.clone(), position, stack })
+            })
+            .collect()
+    }
+
+    /// Returns whether `configuration` accepts an input of length `input_len`.
+    pub fn is_accepting(&self, configuration: &Configuration, input_len: usize) -> bool {
+        configuration.position == input_len
+            && match self.acceptance {
+                Acceptance::FinalState => self.final_states.contains(&configuration.state),
+                Acceptance::EmptyStack => configuration.stack.is_empty(),
+            }
+    }
+
+    /// Searches for an accepting run on `input`, exploring configurations breadth-first and
+    /// giving up after exploring `max_steps` of them.
+    pub fn run<S>(&self, input: &[S], max_steps: usize) -> PushdownOutcome
+    where
+        S: AsRef<str>,
+    {
+        let initial_configuration = self.initial_configuration();
+
+        // Each explored configuration, with the index of the configuration it was reached from.
+        let mut explored = Vec::<(Configuration, Option<usize>)>::new();
+        let mut seen = HashSet::from([initial_configuration.clone()]);
+        let mut queue = VecDeque::from([(initial_configuration, None::<usize>)]);
+
+        while let Some((configuration, parent)) = queue.pop_front() {
+            if explored.len() == max_steps {
+                return PushdownOutcome::StepLimitReached;
+            }
+
+            if self.is_accepting(&configuration, input.len()) {
+                let mut run = vec![configuration];
+                let mut parent = parent;
+
+                while let Some(index) = parent {
+                    let (configuration, next_parent) = &explored[index];
+                    run.push(configuration.clone());
+                    parent = *next_parent;
+                }
+
+                run.reverse();
+                return PushdownOutcome::Accepted(run);
+            }
+
+            for successor in self.successors(&configuration, input) {
+                if seen.insert(successor.clone()) {
+                    queue.push_back((successor, Some(explored.len())));
+                }
+            }
+
+            explored.push((configuration, parent));
+        }
+
+        PushdownOutcome::Rejected
+    }
+}
+
+/// Displays the configurations of a run, as `(state, remaining input, stack)` with the top of
+/// the stack first.
+pub struct ConfigurationTrace<'a, S> {
+    /// Configurations of the run.
+    pub configurations: &'a [Configuration],
+    /// Input of the run.
+    pub input: &'a [S],
+}
+
+impl<S> fmt::Display for ConfigurationTrace<'_, S>
+where
+    S: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, configuration) in self.configurations.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+                write!(f, "|- ")?;
+            }
+
+            let remaining = self.input[configuration.position..]
+                .iter()
+                .map(|symbol| symbol.as_ref())
+                .collect::<String>();
+            let stack = configuration.stack.iter().rev().cloned().collect::<String>();
+
+            write!(
+                f,
+                "({}, {}, {})",
+                configuration.state,
+                if remaining.is_empty() { "ε" } else { &remaining },
+                if stack.is_empty() { "ε" } else { &stack }
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TryFrom<SerializedPushdownAutomaton> for PushdownAutomaton {
+    type Error = StateMachineDeserError;
+
+    fn try_from(automaton: SerializedPushdownAutomaton) -> Result<Self, Self::Error> {
+        let check_state = |state: &String| {
+            if automaton.states.contains(state) {
+                Ok(())
+            } else {
+                Err(StateMachineDeserError::InvalidState(state.clone()))
+            }
+        };
+
+        let check_stack_symbol = |symbol: &String| {
+            if automaton.stack_alphabet.contains(symbol) {
+                Ok(())
+            } else {
+                Err(StateMachineDeserError::InvalidSymbol(symbol.clone()))
+            }
+        };
+
+        check_state(&automaton.initial_state)?;
+        automaton.final_states.iter().try_for_each(check_state)?;
+        automaton.initial_stack.iter().try_for_each(check_stack_symbol)?;
+
+        let mut transitions = HashMap::<String, Vec<PushdownTransition>>::new();
+
+        for transition in automaton.transitions.iter() {
+            check_state(&transition.src_state)?;
+            check_state(&transition.dst_state)?;
+            transition.pop.iter().try_for_each(check_stack_symbol)?;
+            transition.push.iter().try_for_each(check_stack_symbol)?;
+
+            if let Some(symbol) = transition.symbol.as_ref() {
+                if !automaton.alphabet.contains(symbol) {
+                    return Err(StateMachineDeserError::InvalidSymbol(symbol.clone()));
+                }
+            }
+
+            transitions.entry(transition.src_state.clone()).or_default().push(transition.clone());
+        }
+
+        Ok(Self {
+            states: automaton.states,
+            alphabet: automaton.alphabet,
+            stack_alphabet: automaton.stack_alphabet,
+            transitions,
+            initial_state: automaton.initial_state,
+            initial_stack: automaton.initial_stack,
+            final_states: automaton.final_states,
+            acceptance: automaton.acceptance,
+        })
+    }
+}
+
+impl From<PushdownAutomaton> for SerializedPushdownAutomaton {
+    fn from(automaton: PushdownAutomaton) -> Self {
+        Self {
+            states: automaton.states,
+            alphabet: automaton.alphabet,
+            stack_alphabet: automaton.stack_alphabet,
+            transitions: automaton.transitions.into_values().flatten().collect(),
+            initial_state: automaton.initial_state,
+            initial_stack: automaton.initial_stack,
+            final_states: automaton.final_states,
+            acceptance: automaton.acceptance,
+        }
+    }
+}
+
+/// Serialized form of a pushdown automaton.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct SerializedPushdownAutomaton {
+    states: BTreeSet<String>,
+    alphabet: BTreeSet<String>,
+    stack_alphabet: BTreeSet<String>,
+    transitions: BTreeSet<PushdownTransition>,
+    initial_state: String,
+    #[serde(default)]
+    initial_stack: Vec<String>,
+    #[serde(default)]
+    final_states: BTreeSet<String>,
+    #[serde(default)]
+    acceptance: Acceptance,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    fn brackets() -> PushdownAutomaton {
+        serde_json::from_str(include_str!("../../../state-machines/brackets.json")).unwrap()
+    }
+
+    #[test]
+    fn test_brackets() {
+        let automaton = brackets();
+
+        for input in ["", "()", "{[()]}", "()[]{}", "{()[{}]}"] {
+            let sequence = utils::str_to_grapheme_clusters(input);
+            assert!(automaton.run(&sequence, 1000).is_accepted(), "{}", input);
+        }
+
+        for input in ["(", ")", "(]", "{[}]", "(()"] {
+            let sequence = utils::str_to_grapheme_clusters(input);
+            assert_eq!(automaton.run(&sequence, 1000), PushdownOutcome::Rejected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let sequence = utils::str_to_grapheme_clusters("[()]");
+
+        let configurations = match brackets().run(&sequence, 1000) {
+            PushdownOutcome::Accepted(configurations) => configurations,
+            outcome => panic!("{:?}", outcome),
+        };
+
+        let trace = ConfigurationTrace { configurations: &configurations, input: &sequence };
+        let expected = "\
+(q, [()], Z)
+|- (q, ()], [Z)
+|- (q, )], ([Z)
+|- (q, ], [Z)
+|- (q, ε, Z)
+|- (f, ε, Z)";
+
+        assert_eq!(trace.to_string(), expected);
+    }
+
+    #[test]
+    fn test_empty_stack_and_step_limit() {
+        // Accepts a^n b^n by empty stack, and can push `A` forever using an epsilon transition.
+        let json = r#"{
+            "states": ["p", "q"],
+            "alphabet": ["a", "b"],
+            "stack_alphabet": ["A", "Z"],
+            "transitions": [
+                { "src_state": "p", "symbol": "a", "push": ["A"], "dst_state": "p" },
+                { "src_state": "p", "push": ["A"], "dst_state": "p" },
+                { "src_state": "p", "dst_state": "q" },
+                { "src_state": "q", "symbol": "b", "pop": "A", "dst_state": "q" },
+                { "src_state": "q", "pop": "Z", "dst_state": "q" }
+            ],
+            "initial_state": "p",
+            "initial_stack": ["Z"],
+            "acceptance": "empty_stack"
+        }"#;
+
+        let automaton = serde_json::from_str::<PushdownAutomaton>(json).unwrap();
+        assert_eq!(automaton.acceptance(), Acceptance::EmptyStack);
+        assert!(automaton.run(&["a", "a", "b", "b"], 1000).is_accepted());
+
+        // The epsilon loop keeps producing new configurations, so the search never ends on its
+        // own when the input is rejected.
+        assert_eq!(automaton.run(&["a", "b", "b", "a"], 1000), PushdownOutcome::StepLimitReached);
+
+        let invalid = json.replace(r#""pop": "A""#, r#""pop": "B""#);
+        assert!(serde_json::from_str::<PushdownAutomaton>(&invalid).is_err());
+    }
+}
@@ -0,0 +1,675 @@
+use crate::state_machine::{Nfa, StateMachine};
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Regular expression over the symbols of a state machine alphabet.
+///
+/// Supports concatenation, alternation (`|`), repetition (`*`, `+`, `?`), grouping (`(...)`),
+/// character classes with ranges (`[_a-zA-Z]`) and escaping of special characters (`\*`).
+/// Symbols made of several characters are written in braces (`{ab}`), and the empty class `[]`
+/// matches no sequence.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Regexp {
+    /// Matches no sequence.
+    Empty,
+    /// Matches only the empty sequence.
+    Epsilon,
+    /// Matches a single symbol.
+    Symbol(String),
+    /// Matches the concatenation of the sequences matched by each regular expression.
+    Concat(Vec<Regexp>),
+    /// Matches the sequences matched by any of the regular expressions.
+    Alternation(Vec<Regexp>),
+    /// Matches zero or more repetitions.
+    Star(Box<Regexp>),
+    /// Matches one or more repetitions.
+    Plus(Box<Regexp>),
+    /// Matches zero or one repetition.
+    Optional(Box<Regexp>),
+}
+
+impl Regexp {
+    /// Parses a regular expression.
+    pub fn parse(pattern: &str) -> Result<Self, RegexpError> {
+        let mut parser = Parser { chars: pattern.chars().collect(), position: 0 };
+        let regexp = parser.parse_alternation()?;
+
+        match parser.peek() {
+            Some(')') => Err(RegexpError::new("Unmatched ')'", parser.position)),
+            Some(c) => Err(RegexpError::new(format!("Unexpected '{}'", c), parser.position)),
+            None => Ok(regexp),
+        }
+    }
+
+    /// Builds a nondeterministic state machine accepting the same language, using Thompson's
+    /// construction.
+    pub fn to_nfa(&self) -> Nfa {
+        let mut nfa = Nfa::new();
+        let (start, end) = self.build(&mut nfa);
+
+        nfa.add_transition(nfa.initial_state(), None, start);
+        nfa.set_final(end, true);
+        nfa
+    }
+
+    /// Adds the states and transitions matching the regular expression to `nfa`. Returns the
+    /// entry and exit states of the added fragment.
+    fn build(&self, nfa: &mut Nfa) -> (usize, usize) {
+        let start = nfa.add_state();
+
+        let end = match self {
+            Self::Empty => nfa.add_state(),
+            Self::Epsilon => {
+                let end = nfa.add_state();
+                nfa.add_transition(start, None, end);
+                end
+            }
+            Self::Symbol(symbol) => {
+                let end = nfa.add_state();
+                nfa.add_transition(start, Some(symbol), end);
+                end
+            }
+            Self::Concat(regexps) => {
+                let mut end = start;
+
+                for regexp in regexps.iter() {
+                    let (inner_start, inner_end) = regexp.build(nfa);
+                    nfa.add_transition(end, None, inner_start);
+                    end = inner_end;
+                }
+
+                end
+            }
+            Self::Alternation(regexps) => {
+                let end = nfa.add_state();
+
+                for regexp in regexps.iter() {
+                    let (inner_start, inner_end) = regexp.build(nfa);
+                    nfa.add_transition(start, None, inner_start);
+                    nfa.add_transition(inner_end, None, end);
+                }
+
+                end
+            }
+            Self::Star(regexp) | Self::Plus(regexp) | Self::Optional(regexp) => {
+                let end = nfa.add_state();
+                let (inner_start, inner_end) = regexp.build(nfa);
+
+                nfa.add_transition(start, None, inner_start);
+                nfa.add_transition(inner_end, None, end);
+
+                if !matches!(self, Self::Plus(_)) {
+                    nfa.add_transition(start, None, end);
+                }
+
+                if !matches!(self, Self::Optional(_)) {
+                    nfa.add_transition(inner_end, None, inner_start);
+                }
+
+                end
+            }
+        };
+
+        (start, end)
+    }
+
+    /// Returns the concatenation of `self` and `other`, simplified by dropping epsilons and
+    /// merging `x x*` and `x* x` into `x+`.
+    pub fn concat(self, other: Regexp) -> Regexp {
+        let mut regexps = Vec::<Regexp>::new();
+
+        for regexp in [self, other] {
+            match regexp {
+                Self::Empty => return Self::Empty,
+                Self::Epsilon => (),
+                Self::Concat(inner) => regexps.extend(inner),
+                regexp => regexps.push(regexp),
+            }
+        }
+
+        let mut merged = Vec::<Regexp>::new();
+
+        for regexp in regexps {
+            let is_plus = match (merged.last(), &regexp) {
+                (Some(last), Self::Star(inner)) => last == inner.as_ref(),
+                (Some(Self::Star(inner)), regexp) => inner.as_ref() == regexp,
+                _ => false,
+            };
+
+            if is_plus {
+                merged.pop();
+                merged.push(match regexp {
+                    Self::Star(inner) => Self::Plus(inner),
+                    regexp => Self::Plus(Box::new(regexp)),
+                });
+            } else {
+                merged.push(regexp);
+            }
+        }
+
+        match merged.len() {
+            0 => Self::Epsilon,
+            1 => merged.remove(0),
+            _ => Self::Concat(merged),
+        }
+    }
+
+    /// Returns the alternation of `self` and `other`, simplified by dropping empty and
+    /// duplicate alternatives and turning `x|()` into `x?`.
+    pub fn alternation(self, other: Regexp) -> Regexp {
+        let mut regexps = Vec::<Regexp>::new();
+        let mut has_epsilon = false;
+
+        for regexp in [self, other] {
+            let inner = match regexp {
+                Self::Empty => Vec::new(),
+                Self::Alternation(inner) => inner,
+                Self::Optional(inner) => {
+                    has_epsilon = true;
+                    vec![*inner]
+                }
+                regexp => vec![regexp],
+            };
+
+            for regexp in inner {
+                if regexp == Self::Epsilon {
+                    has_epsilon = true;
+                } else if !regexps.contains(&regexp) {
+                    regexps.push(regexp);
+                }
+            }
+        }
+
+        let regexp = match regexps.len() {
+            0 => return if has_epsilon { Self::Epsilon } else { Self::Empty },
+            1 => regexps.remove(0),
+            _ => Self::Alternation(regexps),
+        };
+
+        if has_epsilon && !regexp.is_nullable() {
+            Self::Optional(Box::new(regexp))
+        } else {
+            regexp
+        }
+    }
+
+    /// Returns zero or more repetitions of `self`, simplified.
+    pub fn star(self) -> Regexp {
+        match self {
+            Self::Empty | Self::Epsilon => Self::Epsilon,
+            Self::Star(inner) | Self::Plus(inner) | Self::Optional(inner) => Self::Star(inner),
+            regexp => Self::Star(Box::new(regexp)),
+        }
+    }
+
+    /// Returns whether the regular expression matches the empty sequence.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            Self::Empty | Self::Symbol(_) => false,
+            Self::Epsilon | Self::Star(_) | Self::Optional(_) => true,
+            Self::Concat(regexps) => regexps.iter().all(Self::is_nullable),
+            Self::Alternation(regexps) => regexps.iter().any(Self::is_nullable),
+            Self::Plus(regexp) => regexp.is_nullable(),
+        }
+    }
+
+    /// Returns the characters of an alternation made only of single-character symbols, which
+    /// can be displayed as a character class.
+    fn class_chars(&self) -> Option<Vec<char>> {
+        match self {
+            Self::Alternation(regexps) => regexps.iter().map(Self::single_char).collect(),
+            _ => None,
+        }
+    }
+
+    /// Returns the character of a single-character symbol.
+    fn single_char(&self) -> Option<char> {
+        match self {
+            Self::Symbol(symbol) => {
+                let mut chars = symbol.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether the regular expression can be followed by a repetition operator without
+    /// being wrapped in parentheses.
+    fn is_atomic(&self) -> bool {
+        matches!(self, Self::Empty | Self::Epsilon | Self::Symbol(_))
+            || self.class_chars().is_some()
+    }
+}
+
+impl FromStr for Regexp {
+    type Err = RegexpError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Self::parse(pattern)
+    }
+}
+
+impl fmt::Display for Regexp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "[]"),
+            Self::Epsilon => write!(f, "()"),
+            Self::Symbol(symbol) => match self.single_char() {
+                Some(c) if SPECIAL_CHARS.contains(c) => write!(f, "\\{}", c),
+                Some(c) => write!(f, "{}", c),
+                None => {
+                    write!(f, "{{")?;
+
+                    for c in symbol.chars() {
+                        if matches!(c, '}' | '\\') {
+                            write!(f, "\\")?;
+                        }
+
+                        write!(f, "{}", c)?;
+                    }
+
+                    write!(f, "}}")
+                }
+            },
+            Self::Concat(regexps) => {
+                for regexp in regexps.iter() {
+                    if matches!(regexp, Self::Alternation(_)) && !regexp.is_atomic() {
+                        write!(f, "({})", regexp)?;
+                    } else {
+                        write!(f, "{}", regexp)?;
+                    }
+                }
+
+                Ok(())
+            }
+            Self::Alternation(regexps) => {
+                // Single-character alternatives are grouped into a character class.
+                let (chars, others) =
+                    regexps.iter().partition::<Vec<_>, _>(|regexp| regexp.single_char().is_some());
+
+                let mut separator = "";
+
+                if chars.len() >= 2 {
+                    write_class(f, chars.iter().filter_map(|regexp| regexp.single_char()))?;
+                    separator = "|";
+                } else if let Some(regexp) = chars.first() {
+                    write!(f, "{}", regexp)?;
+                    separator = "|";
+                }
+
+                for regexp in others {
+                    write!(f, "{}{}", separator, regexp)?;
+                    separator = "|";
+                }
+
+                Ok(())
+            }
+            Self::Star(regexp) | Self::Plus(regexp) | Self::Optional(regexp) => {
+                if regexp.is_atomic() {
+                    write!(f, "{}", regexp)?;
+                } else {
+                    write!(f, "({})", regexp)?;
+                }
+
+                match self {
+                    Self::Star(_) => write!(f, "*"),
+                    Self::Plus(_) => write!(f, "+"),
+                    _ => write!(f, "?"),
+                }
+            }
+        }
+    }
+}
+
+/// Characters that must be escaped to be matched literally.
+const SPECIAL_CHARS: &str = "()[]{}|*+?\\";
+
+/// Writes `chars` as a character class, collapsing runs of three or more consecutive characters
+/// into ranges.
+fn write_class<I>(f: &mut fmt::Formatter<'_>, chars: I) -> fmt::Result
+where
+    I: Iterator<Item = char>,
+{
+    let chars = chars.collect::<BTreeSet<_>>().into_iter().collect::<Vec<_>>();
+
+    let write_char = |f: &mut fmt::Formatter<'_>, c: char| {
+        if matches!(c, ']' | '\\' | '-' | '^') {
+            write!(f, "\\{}", c)
+        } else {
+            write!(f, "{}", c)
+        }
+    };
+
+    write!(f, "[")?;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let mut j = i;
+        while j + 1 < chars.len() && u32::from(chars[j + 1]) == u32::from(chars[j]) + 1 {
+            j += 1;
+        }
+
+        if j - i >= 2 {
+            write_char(f, chars[i])?;
+            write!(f, "-")?;
+            write_char(f, chars[j])?;
+        } else {
+            for &c in chars[i..=j].iter() {
+                write_char(f, c)?;
+            }
+        }
+
+        i = j + 1;
+    }
+
+    write!(f, "]")
+}
+
+impl StateMachine {
+    /// Compiles a regular expression into a minimal deterministic state machine.
+    pub fn from_regexp(pattern: &str) -> Result<Self, RegexpError> {
+        Ok(Regexp::parse(pattern)?.to_nfa().to_state_machine().minimize())
+    }
+}
+
+/// Error returned when a regular expression cannot be parsed.
+#[derive(Clone, Debug)]
+pub struct RegexpError {
+    /// Error message.
+    message: String,
+    /// Position of the character at which the error occurred.
+    position: usize,
+}
+
+impl RegexpError {
+    /// Creates a new error.
+    pub fn new<S>(message: S, position: usize) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { message: message.into(), position }
+    }
+}
+
+impl fmt::Display for RegexpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Error at position {}: {}", self.position, self.message)
+    }
+}
+
+impl Error for RegexpError {}
+
+/// Recursive descent parser for regular expressions.
+struct Parser {
+    /// Characters of the pattern.
+    chars: Vec<char>,
+    /// Position of the next character to parse.
+    position: usize,
+}
+
+impl Parser {
+    /// Returns the next character without consuming it.
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /// Consumes and returns the next character.
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    /// Parses `concat ('|' concat)*`.
+    fn parse_alternation(&mut self) -> Result<Regexp, RegexpError> {
+        let mut regexps = vec![self.parse_concat()?];
+
+        while self.peek() == Some('|') {
+            self.next();
+            regexps.push(self.parse_concat()?);
+        }
+
+        Ok(if regexps.len() == 1 { regexps.remove(0) } else { Regexp::Alternation(regexps) })
+    }
+
+    /// Parses `repeat*`.
+    fn parse_concat(&mut self) -> Result<Regexp, RegexpError> {
+        let mut regexps = Vec::new();
+
+        while !matches!(self.peek(), None | Some('|') | Some(')')) {
+            regexps.push(self.parse_repeat()?);
+        }
+
+        Ok(match regexps.len() {
+            0 => Regexp::Epsilon,
+            1 => regexps.remove(0),
+            _ => Regexp::Concat(regexps),
+        })
+    }
+
+    /// Parses `atom ('*' | '+' | '?')*`.
+    fn parse_repeat(&mut self) -> Result<Regexp, RegexpError> {
+        let mut regexp = self.parse_atom()?;
+
+        loop {
+            regexp = match self.peek() {
+                Some('*') => Regexp::Star(Box::new(regexp)),
+                Some('+') => Regexp::Plus(Box::new(regexp)),
+                Some('?') => Regexp::Optional(Box::new(regexp)),
+                _ => break,
+            };
+
+            self.next();
+        }
+
+        Ok(regexp)
+    }
+
+    /// Parses a symbol, an escaped symbol, a symbol in braces, a group or a character class.
+    fn parse_atom(&mut self) -> Result<Regexp, RegexpError> {
+        let position = self.position;
+
+        match self.next() {
+            Some('(') => {
+                let regexp = self.parse_alternation()?;
+
+                match self.next() {
+                    Some(')') => Ok(regexp),
+                    _ => Err(RegexpError::new("Unclosed group", position)),
+                }
+            }
+            Some('[') => self.parse_class(position),
+            Some('{') => self.parse_braced(position),
+            Some('\\') => match self.next() {
+                Some(c) => Ok(Regexp::Symbol(c.to_string())),
+                None => Err(RegexpError::new("Dangling escape", position)),
+            },
+            Some(']') => Err(RegexpError::new("Unmatched ']'", position)),
+            Some('}') => Err(RegexpError::new("Unmatched '}'", position)),
+            Some('*' | '+' | '?') => Err(RegexpError::new("Nothing to repeat", position)),
+            Some(c) => Ok(Regexp::Symbol(c.to_string())),
+            None => Err(RegexpError::new("Unexpected end of pattern", position)),
+        }
+    }
+
+    /// Parses the contents of a symbol in braces started at `start`, after the `{`. Inside the
+    /// braces, only `}` and `\` need to be escaped.
+    fn parse_braced(&mut self, start: usize) -> Result<Regexp, RegexpError> {
+        let mut symbol = String::new();
+
+        loop {
+            match self.next() {
+                Some('}') => break,
+                Some('\\') => match self.next() {
+                    Some(c) => symbol.push(c),
+                    None => return Err(RegexpError::new("Dangling escape", self.position)),
+                },
+                Some(c) => symbol.push(c),
+                None => return Err(RegexpError::new("Unclosed symbol", start)),
+            }
+        }
+
+        if symbol.is_empty() {
+            return Err(RegexpError::new("Empty symbol", start));
+        }
+
+        Ok(Regexp::Symbol(symbol))
+    }
+
+    /// Parses the contents of a character class started at `start`, after the `[`. The empty
+    /// class `[]` matches no sequence.
+    fn parse_class(&mut self, start: usize) -> Result<Regexp, RegexpError> {
+        let mut symbols = BTreeSet::<char>::new();
+
+        if self.peek() == Some('^') {
+            return Err(RegexpError::new("Negated character classes are not supported", start));
+        }
+
+        loop {
+            let position = self.position;
+
+            let first = match self.next() {
+                Some(']') => break,
+                Some('\\') => self.next(),
+                c => c,
+            }
+            .ok_or_else(|| RegexpError::new("Unclosed character class", start))?;
+
+            let is_range = self.peek() == Some('-')
+                && !matches!(self.chars.get(self.position + 1), None | Some(']'));
+
+            if is_range {
+                self.next();
+
+                let last = match self.next() {
+                    Some('\\') => self.next(),
+                    c => c,
+                }
+                .ok_or_else(|| RegexpError::new("Unclosed character class", start))?;
+
+                if first > last {
+                    return Err(RegexpError::new(
+                        format!("Invalid range '{}-{}'", first, last),
+                        position,
+                    ));
+                }
+
+                symbols.extend(first..=last);
+            } else {
+                symbols.insert(first);
+            }
+        }
+
+        let mut symbols =
+            symbols.into_iter().map(|c| Regexp::Symbol(c.to_string())).collect::<Vec<_>>();
+
+        Ok(match symbols.len() {
+            0 => Regexp::Empty,
+            1 => symbols.remove(0),
+            _ => Regexp::Alternation(symbols),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+    use crate::utils;
+
+    /// Returns all strings of length at most `max_len` over `alphabet`.
+    fn all_strings(alphabet: &[char], max_len: usize) -> Vec<String> {
+        let mut strings = vec![String::new()];
+        let mut last = vec![String::new()];
+
+        for _ in 0..max_len {
+            last = last
+                .iter()
+                .flat_map(|prefix| alphabet.iter().map(move |c| format!("{}{}", prefix, c)))
+                .collect();
+
+            strings.extend(last.iter().cloned());
+        }
+
+        strings
+    }
+
+    #[test]
+    fn test_against_regex_crate() {
+        let patterns = [
+            r#""[_a-zA-Z0-9]*""#,
+            r"'[_a-zA-Z0-9]'",
+            r"_[_a-zA-Z0-9]+|[a-zA-Z][_a-zA-Z0-9]*",
+            r"0|[1-9][0-9]*",
+            r"(a|_)*9?|Z+'",
+            r"(a(_|Z)?)+|\(|",
+            r"[-a]|[a\]]",
+        ];
+
+        let alphabet = ['_', 'a', 'Z', '0', '9', '"', '\'', '(', '-', ']'];
+        let strings = all_strings(&alphabet, 4);
+
+        for pattern in patterns {
+            let state_machine = StateMachine::from_regexp(pattern).unwrap();
+            let regex = regex::Regex::new(&format!("^(?:{})$", pattern)).unwrap();
+
+            for string in strings.iter() {
+                assert_eq!(
+                    state_machine.is_accepted(&utils::str_to_grapheme_clusters(string)),
+                    regex.is_match(string),
+                    "pattern {} disagrees on {}",
+                    pattern,
+                    string,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_identifier_state_machine() {
+        let identifier = fixtures::identifier();
+
+        let compiled = StateMachine::from_regexp("_[_a1]+|a[_a1]*").unwrap();
+        assert_eq!(identifier.equivalent(&compiled), Ok(()));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let symbol = |symbol: &str| Regexp::Symbol(symbol.to_owned());
+
+        let regexps = [
+            Regexp::Empty,
+            Regexp::Epsilon,
+            Regexp::Concat(vec![symbol("ab"), Regexp::Empty, symbol("{")]),
+            Regexp::Alternation(vec![
+                symbol("*"),
+                symbol("a}\\"),
+                Regexp::Star(Box::new(symbol("é"))),
+            ]),
+            Regexp::Plus(Box::new(symbol("while"))),
+        ];
+
+        for regexp in regexps {
+            assert_eq!(Regexp::parse(&regexp.to_string()).unwrap(), regexp, "{}", regexp);
+        }
+
+        assert_eq!(Regexp::Empty.to_string(), "[]");
+        assert_eq!(symbol("a}\\").to_string(), "{a\\}\\\\}");
+        assert!(StateMachine::from_regexp("[]").unwrap().is_empty());
+
+        // Multi-character symbols read back as single symbols.
+        let words = StateMachine::from_fa_str("p q\nif else\np\nq\np if q\nq else q\n").unwrap();
+        let regexp = words.to_regexp().to_string();
+        assert_eq!(regexp, "{if}{else}*");
+        assert_eq!(StateMachine::from_regexp(&regexp).unwrap().equivalent(&words), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for pattern in ["(a", "a)", "*a", "a|+", "[a", "[z-a]", "[^a]", "a\\", "{ab", "{}", "a}"] {
+            assert!(Regexp::parse(pattern).is_err(), "{} should not parse", pattern);
+        }
+    }
+}
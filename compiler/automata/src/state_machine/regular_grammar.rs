@@ -0,0 +1,319 @@
+use crate::grammar::{Grammar, GrammarError, NonTerminal, Production, Symbol, Terminal};
+use crate::state_machine::{Nfa, StateMachine};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Right-linear view of a [Grammar], such as the grammars of the README. Every production is
+/// made of terminals, optionally followed by a single nonterminal, and every literal terminal is
+/// a single symbol of the state machine alphabet.
+#[derive(Clone, Debug)]
+pub struct RegularGrammar {
+    /// The underlying grammar, which is right-linear.
+    grammar: Grammar,
+}
+
+impl RegularGrammar {
+    /// Builds the right-linear view of `grammar`. Nonterminals used before the end of a
+    /// production are replaced by their alternatives, which only works if they are not
+    /// recursive. Returns an error if the grammar is not context-free or uses tokens, which
+    /// [Grammar::parse] reads undefined nonterminals as.
+    pub fn new(grammar: &Grammar) -> Result<Self, GrammarError> {
+        if !grammar.is_context_free() {
+            return Err(GrammarError::new("Regular grammars must be context-free", 0));
+        }
+
+        if let Some(Terminal::Token(name)) =
+            grammar.terminals().into_iter().find(|terminal| matches!(terminal, Terminal::Token(_)))
+        {
+            return Err(GrammarError::new(format!("Undefined nonterminal <{}>", name), 0));
+        }
+
+        if grammar.is_right_linear() {
+            return Ok(Self { grammar: grammar.clone() });
+        }
+
+        let productions = inline_nonterminals(grammar)?;
+        let grammar = Grammar::new(grammar.start_symbol().clone(), productions);
+        debug_assert!(grammar.is_right_linear());
+
+        Ok(Self { grammar })
+    }
+
+    /// Parses a grammar with the given start symbol, written without angle brackets, and builds
+    /// its right-linear view.
+    pub fn parse(input: &str, start_symbol: &str) -> Result<Self, GrammarError> {
+        let mut grammar = Grammar::parse(input)?;
+        grammar.set_start_symbol(start_symbol)?;
+
+        Self::new(&grammar)
+    }
+
+    /// Returns the underlying right-linear grammar.
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Builds a nondeterministic state machine with one state for each nonterminal.
+    pub fn to_nfa(&self) -> Nfa {
+        let mut nfa = Nfa::new();
+        let mut states = HashMap::<&NonTerminal, usize>::new();
+        states.insert(self.grammar.start_symbol(), nfa.initial_state());
+
+        for nonterminal in self.grammar.nonterminals() {
+            if !states.contains_key(nonterminal) {
+                states.insert(nonterminal, nfa.add_state());
+            }
+        }
+
+        let final_state = nfa.add_state();
+        nfa.set_final(final_state, true);
+
+        for production in self.grammar.productions() {
+            let rhs = production.rhs.as_slice();
+            let (terminals, next_state) = match rhs.split_last() {
+                Some((Symbol::NonTerminal(nonterminal), terminals)) => {
+                    (terminals, states[nonterminal])
+                }
+                _ => (rhs, final_state),
+            };
+
+            let mut state = states[production.nonterminal().unwrap()];
+
+            for (i, symbol) in terminals.iter().enumerate() {
+                let dst_state = if i + 1 == terminals.len() { next_state } else { nfa.add_state() };
+
+                if let Symbol::Terminal(Terminal::Literal(text)) = symbol {
+                    nfa.add_transition(state, Some(text), dst_state);
+                }
+
+                state = dst_state;
+            }
+
+            if terminals.is_empty() {
+                nfa.add_transition(state, None, next_state);
+            }
+        }
+
+        nfa
+    }
+
+    /// Builds a minimal deterministic state machine accepting the language of the grammar.
+    pub fn to_state_machine(&self) -> StateMachine {
+        self.to_nfa().to_state_machine().minimize()
+    }
+}
+
+impl From<&StateMachine> for RegularGrammar {
+    /// Builds a right-linear grammar with one nonterminal for each state, named after it.
+    /// Transitions into dead states are left out.
+    fn from(state_machine: &StateMachine) -> Self {
+        let productive = state_machine.productive_states();
+        let symbols = state_machine.sorted_symbols();
+
+        let mut states = state_machine.sorted_states();
+        states.retain(|&state| state != state_machine.initial_state());
+        states.insert(0, state_machine.initial_state());
+
+        let nonterminal = |state: &str| Symbol::NonTerminal(NonTerminal(state.to_owned()));
+        let mut productions = Vec::new();
+
+        for state in states.into_iter().filter(|state| productive.contains(state)) {
+            for &symbol in symbols.iter() {
+                if let Some(next_state) = state_machine.next_state(state, symbol) {
+                    if productive.contains(next_state) {
+                        let rhs = vec![
+                            Symbol::Terminal(Terminal::Literal(symbol.to_owned())),
+                            nonterminal(next_state),
+                        ];
+
+                        productions.push(Production { lhs: vec![nonterminal(state)], rhs });
+                    }
+                }
+            }
+
+            if state_machine.final_states.contains(state) {
+                productions.push(Production { lhs: vec![nonterminal(state)], rhs: Vec::new() });
+            }
+        }
+
+        let start_symbol = NonTerminal(state_machine.initial_state().to_owned());
+        Self { grammar: Grammar::new(start_symbol, productions) }
+    }
+}
+
+impl fmt::Display for RegularGrammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.grammar)
+    }
+}
+
+/// Returns the productions of the context-free `grammar`, with the nonterminals used before the
+/// end of a production replaced by their alternatives.
+fn inline_nonterminals(grammar: &Grammar) -> Result<Vec<Production>, GrammarError> {
+    let recursive = recursive_nonterminals(grammar);
+    let mut productions = grammar.productions().to_vec();
+
+    loop {
+        let position = productions.iter().enumerate().find_map(|(i, production)| {
+            let rhs = &production.rhs;
+
+            rhs.iter()
+                .take(rhs.len().saturating_sub(1))
+                .position(|symbol| matches!(symbol, Symbol::NonTerminal(_)))
+                .map(|j| (i, j))
+        });
+
+        let (i, j) = match position {
+            Some(position) => position,
+            None => break,
+        };
+
+        let production = productions.remove(i);
+        let inlined = match &production.rhs[j] {
+            Symbol::NonTerminal(inlined) => inlined,
+            Symbol::Terminal(_) => unreachable!(),
+        };
+
+        if recursive.contains(inlined) {
+            return Err(GrammarError::new(
+                format!(
+                    "Recursive nonterminal {} is used before the end of a production of {}",
+                    inlined,
+                    production.nonterminal().unwrap()
+                ),
+                0,
+            ));
+        }
+
+        let replacements = grammar
+            .productions_for(inlined)
+            .map(|(_, inlined_production)| {
+                let mut rhs = production.rhs[..j].to_vec();
+                rhs.extend(inlined_production.rhs.iter().cloned());
+                rhs.extend(production.rhs[(j + 1)..].iter().cloned());
+                Production { lhs: production.lhs.clone(), rhs }
+            })
+            .collect::<Vec<_>>();
+
+        productions.splice(i..i, replacements);
+    }
+
+    // Inlining may produce the same production more than once.
+    let mut unique_productions = Vec::new();
+    for production in productions {
+        if !unique_productions.contains(&production) {
+            unique_productions.push(production);
+        }
+    }
+
+    Ok(unique_productions)
+}
+
+/// Returns the nonterminals of the context-free `grammar` that can derive a sequence containing
+/// themselves.
+fn recursive_nonterminals(grammar: &Grammar) -> HashSet<&NonTerminal> {
+    let mut edges = HashMap::<&NonTerminal, HashSet<&NonTerminal>>::new();
+    for production in grammar.productions() {
+        for symbol in production.rhs.iter() {
+            if let Symbol::NonTerminal(nonterminal) = symbol {
+                edges.entry(production.nonterminal().unwrap()).or_default().insert(nonterminal);
+            }
+        }
+    }
+
+    grammar
+        .nonterminals()
+        .into_iter()
+        .filter(|&nonterminal| {
+            let mut visited = HashSet::<&NonTerminal>::new();
+            let mut stack =
+                edges.get(nonterminal).into_iter().flatten().copied().collect::<Vec<_>>();
+
+            while let Some(next) = stack.pop() {
+                if next == nonterminal {
+                    return true;
+                }
+
+                if visited.insert(next) {
+                    stack.extend(edges.get(next).into_iter().flatten().copied());
+                }
+            }
+
+            false
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    /// Returns the grammars listed in the code blocks of the README.
+    fn readme_grammars() -> Vec<&'static str> {
+        include_str!("../../../../README.md").split("```").skip(1).step_by(2).collect()
+    }
+
+    #[test]
+    fn test_readme_identifier() {
+        let mut grammar = Grammar::parse(readme_grammars()[1]).unwrap();
+        grammar.set_start_symbol("ident").unwrap();
+        assert!(!grammar.is_right_linear());
+
+        let regular = RegularGrammar::new(&grammar).unwrap();
+        assert!(regular.grammar().is_right_linear());
+
+        // The JSON state machine only uses a subset of the alphabet.
+        let identifier = fixtures::identifier();
+        let restricted =
+            regular.to_state_machine().intersection(&StateMachine::from_regexp("[_a1]*").unwrap());
+
+        assert_eq!(restricted.equivalent(&identifier), Ok(()));
+    }
+
+    #[test]
+    fn test_readme_number() {
+        let grammar = RegularGrammar::parse(readme_grammars()[0], "number").unwrap();
+        let state_machine = grammar.to_state_machine();
+
+        let number = fixtures::number();
+
+        assert_eq!(number.is_subset_of(&state_machine), Ok(()));
+        assert_eq!(
+            state_machine.equivalent(&StateMachine::from_regexp("0|[1-9][0-9]*").unwrap()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_from_state_machine() {
+        let number = fixtures::number();
+
+        let grammar = RegularGrammar::from(&number);
+        assert!(grammar.grammar().is_right_linear());
+
+        let expected = "\
+<initial> ::= 1 <final>
+            | 2 <final>
+
+<final> ::= 0 <final>
+          | 1 <final>
+          | 2 <final>
+          | (nothing)
+";
+        assert_eq!(grammar.to_string(), expected);
+
+        let parsed = RegularGrammar::parse(&grammar.to_string(), "initial").unwrap();
+        assert_eq!(parsed.to_state_machine().equivalent(&number), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(RegularGrammar::parse("<a> ::= x<b>", "a").is_err());
+        assert!(RegularGrammar::parse("<a> ::= x", "b").is_err());
+        assert!(RegularGrammar::parse("| x", "a").is_err());
+        assert!(RegularGrammar::parse("<a> ::= ... | z", "a").is_err());
+        assert!(RegularGrammar::parse("<a> ::= <a>x | x", "a").is_err());
+        assert!(RegularGrammar::parse("<a> x ::= y\n<a> ::= z", "a").is_err());
+    }
+}
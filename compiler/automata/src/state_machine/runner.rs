@@ -0,0 +1,81 @@
+use crate::state_machine::StateMachine;
+
+/// Runs a state machine one symbol at a time.
+///
+/// Once a symbol without a matching transition is fed, the runner is stuck and ignores all
+/// further symbols until it is reset.
+#[derive(Clone)]
+pub struct Runner<'a> {
+    /// The state machine being run.
+    state_machine: &'a StateMachine,
+    /// The current state, or [None] if the runner is stuck.
+    state: Option<&'a str>,
+}
+
+impl<'a> Runner<'a> {
+    /// Creates a runner positioned in the initial state of `state_machine`.
+    pub fn new(state_machine: &'a StateMachine) -> Self {
+        Self { state_machine, state: Some(state_machine.initial_state()) }
+    }
+
+    /// Consumes `symbol` and returns whether a matching transition was found.
+    pub fn feed(&mut self, symbol: &str) -> bool {
+        self.state = self.state.and_then(|state| self.state_machine.next_state(state, symbol));
+        self.state.is_some()
+    }
+
+    /// Returns the current state, or [None] if the runner is stuck.
+    pub fn current_state(&self) -> Option<&'a str> {
+        self.state
+    }
+
+    /// Returns whether the symbols fed so far form an accepted sequence.
+    pub fn is_accepting(&self) -> bool {
+        self.state.is_some_and(|state| self.state_machine.final_states.contains(state))
+    }
+
+    /// Returns whether a symbol without a matching transition was fed.
+    pub fn is_stuck(&self) -> bool {
+        self.state.is_none()
+    }
+
+    /// Moves the runner back to the initial state.
+    pub fn reset(&mut self) {
+        self.state = Some(self.state_machine.initial_state());
+    }
+}
+
+impl StateMachine {
+    /// Returns a runner positioned in the initial state.
+    pub fn runner(&self) -> Runner<'_> {
+        Runner::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_runner() {
+        let state_machine = fixtures::number();
+
+        let mut runner = state_machine.runner();
+        assert_eq!(runner.current_state(), Some("initial"));
+        assert!(!runner.is_accepting());
+
+        assert!(runner.feed("1"));
+        assert!(runner.feed("0"));
+        assert_eq!(runner.current_state(), Some("final"));
+        assert!(runner.is_accepting());
+
+        assert!(!runner.feed("3"));
+        assert!(runner.is_stuck());
+        assert!(!runner.is_accepting());
+        assert!(!runner.feed("1"));
+
+        runner.reset();
+        assert!(!runner.is_stuck());
+        assert!(!runner.feed("0"));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::state_machine::StateMachine;
+use std::fmt::Write;
+
+impl StateMachine {
+    /// Returns the transition function as a grid with a row for each state and a column for
+    /// each symbol. The initial state is marked with `->`, final states with `*` and missing
+    /// transitions with `-`.
+    pub fn to_table_string(&self) -> String {
+        let symbols = self.sorted_symbols();
+        let mut states = self.sorted_states();
+        states.retain(|&state| state != self.initial_state());
+        states.insert(0, self.initial_state());
+
+        let mut rows = vec![std::iter::once(String::new())
+            .chain(symbols.iter().map(|&symbol| format!("{:?}", symbol)))
+            .collect::<Vec<_>>()];
+
+        for &state in states.iter() {
+            let initial_marker = if state == self.initial_state() { "->" } else { "  " };
+            let final_marker = if self.final_states.contains(state) { "*" } else { " " };
+
+            let row = std::iter::once(format!("{}{}{}", initial_marker, final_marker, state))
+                .chain(
+                    symbols
+                        .iter()
+                        .map(|symbol| self.next_state(state, symbol).unwrap_or("-").to_owned()),
+                )
+                .collect();
+
+            rows.push(row);
+        }
+
+        let widths = (0..=symbols.len())
+            .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap())
+            .collect::<Vec<_>>();
+
+        let mut output = String::new();
+
+        for (i, row) in rows.iter().enumerate() {
+            let line = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join(" | ");
+
+            writeln!(&mut output, "{}", line.trim_end()).unwrap();
+
+            if i == 0 {
+                let separator =
+                    widths.iter().map(|&width| "-".repeat(width)).collect::<Vec<_>>().join("-+-");
+                writeln!(&mut output, "{}", separator).unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_to_table_string() {
+        let number = fixtures::number();
+
+        let expected = "
+           | \"0\"   | \"1\"   | \"2\"
+-----------+-------+-------+------
+-> initial | -     | final | final
+  *final   | final | final | final
+";
+        assert_eq!(number.to_table_string(), &expected[1..]);
+    }
+}
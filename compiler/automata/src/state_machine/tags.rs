@@ -0,0 +1,141 @@
+use crate::state_machine::StateMachine;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Category of the sequences accepted by a final state, such as `keyword:while` or
+/// `identifier`. When a state stands for several final states with different tags, as after
+/// the subset construction, the tag with the highest priority is kept.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Deserialize, Serialize)]
+pub struct Tag {
+    /// Name of the category.
+    pub name: String,
+    /// Priority of the tag over other tags. Higher priorities win.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+impl Tag {
+    /// Creates a new tag.
+    pub fn new<S>(name: S, priority: i32) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { name: name.into(), priority }
+    }
+
+    /// Returns the tag with the highest priority. Ties are broken by keeping the name that comes
+    /// first alphabetically, so the result does not depend on the order of `tags`.
+    pub fn highest<'a, I>(tags: I) -> Option<&'a Tag>
+    where
+        I: IntoIterator<Item = &'a Tag>,
+    {
+        tags.into_iter().max_by(|tag, other| {
+            tag.priority.cmp(&other.priority).then_with(|| other.name.cmp(&tag.name))
+        })
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (priority {})", self.name, self.priority)
+    }
+}
+
+impl StateMachine {
+    /// Returns the tag of `state`, if it is a tagged final state.
+    pub fn tag(&self, state: &str) -> Option<&Tag> {
+        self.tags.get(state)
+    }
+
+    /// Returns an iterator over all tagged states and their tags.
+    pub fn iter_tags(&self) -> impl Iterator<Item = (&str, &Tag)> {
+        self.tags.iter().map(|(state, tag)| (state.as_str(), tag))
+    }
+
+    /// Returns the tag of the state the sequence leads to, if the sequence is accepted by a
+    /// tagged final state.
+    pub fn classify<S>(&self, sequence: &[S]) -> Option<&Tag>
+    where
+        S: AsRef<str>,
+    {
+        let mut runner = self.runner();
+
+        for symbol in sequence.iter() {
+            if !runner.feed(symbol.as_ref()) {
+                return None;
+            }
+        }
+
+        runner.current_state().and_then(|state| self.tag(state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::Nfa;
+
+    fn tagged(pattern: &str, tag: Tag) -> Nfa {
+        let mut nfa = Nfa::from(&StateMachine::from_regexp(pattern).unwrap());
+
+        for state in nfa.final_states().collect::<Vec<_>>() {
+            nfa.set_tag(state, Some(tag.clone()));
+        }
+
+        nfa
+    }
+
+    #[test]
+    fn test_classify() {
+        let keyword = tagged("while", Tag::new("keyword:while", 1));
+        let identifier = tagged("(w|h|i|l|e)(w|h|i|l|e)*", Tag::new("identifier", 0));
+        let tokens = keyword.union(&identifier).to_state_machine();
+        let minimized = tokens.minimize();
+
+        for state_machine in [&tokens, &minimized] {
+            let classify = |word: &str| {
+                let sequence = word.chars().map(String::from).collect::<Vec<_>>();
+                state_machine.classify(&sequence).map(|tag| tag.name.as_str())
+            };
+
+            assert_eq!(classify("while"), Some("keyword:while"));
+            assert_eq!(classify("whil"), Some("identifier"));
+            assert_eq!(classify("whilee"), Some("identifier"));
+            assert_eq!(classify(""), None);
+            assert_eq!(classify("x"), None);
+        }
+
+        // Only the state reached by "while" differs from the identifier states by its tag.
+        let untagged = StateMachine::from_regexp("(w|h|i|l|e)(w|h|i|l|e)*").unwrap().minimize();
+        assert_eq!(minimized.iter_states().count(), untagged.iter_states().count() + 5);
+    }
+
+    #[test]
+    fn test_tag_serialization() {
+        let json = r#"{
+            "states": ["a", "b"],
+            "alphabet": ["0"],
+            "transitions": [{ "src_state": "a", "symbol": "0", "dst_state": "b" }],
+            "initial_state": "a",
+            "final_states": ["b"],
+            "tags": { "b": { "name": "zero", "priority": 2 } }
+        }"#;
+
+        let state_machine = serde_json::from_str::<StateMachine>(json).unwrap();
+        assert_eq!(state_machine.tag("b"), Some(&Tag::new("zero", 2)));
+
+        let reloaded =
+            serde_json::from_str::<StateMachine>(&state_machine.to_json_string()).unwrap();
+        assert_eq!(reloaded.classify(&["0"]), Some(&Tag::new("zero", 2)));
+
+        let non_final = json.replace(r#""final_states": ["b"]"#, r#""final_states": []"#);
+        assert!(serde_json::from_str::<StateMachine>(&non_final).is_err());
+    }
+
+    #[test]
+    fn test_highest() {
+        let tags = [Tag::new("b", 1), Tag::new("c", 2), Tag::new("a", 2)];
+        assert_eq!(Tag::highest(&tags), Some(&Tag::new("a", 2)));
+        assert_eq!(Tag::highest(&[]), None);
+    }
+}
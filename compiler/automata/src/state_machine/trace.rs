@@ -0,0 +1,151 @@
+use crate::state_machine::StateMachine;
+use std::fmt;
+
+/// Single transition taken while running a state machine.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraceStep {
+    /// State the transition starts from.
+    pub state: String,
+    /// Symbol consumed by the transition.
+    pub symbol: String,
+    /// State the transition ends in.
+    pub next_state: String,
+}
+
+/// Reason a run of a state machine ended.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TraceOutcome {
+    /// The whole sequence was consumed and the run ended in a final state.
+    Accepted,
+    /// The whole sequence was consumed, but the run ended in the given non-final state.
+    EndedInNonFinalState(String),
+    /// No transition exists from `state` on the `symbol` at the zero-based `position`.
+    NoTransition {
+        /// State the run got stuck in.
+        state: String,
+        /// Symbol that could not be consumed.
+        symbol: String,
+        /// Position of the symbol in the sequence.
+        position: usize,
+    },
+}
+
+/// Full path taken by a state machine on a sequence.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trace {
+    /// Transitions taken, in order.
+    pub steps: Vec<TraceStep>,
+    /// Reason the run ended.
+    pub outcome: TraceOutcome,
+}
+
+impl Trace {
+    /// Returns whether the traced sequence was accepted.
+    pub fn is_accepted(&self) -> bool {
+        self.outcome == TraceOutcome::Accepted
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in self.steps.iter() {
+            writeln!(f, "{} ---({})--> {}", step.state, step.symbol, step.next_state)?;
+        }
+
+        write!(f, "{}", self.outcome)
+    }
+}
+
+impl fmt::Display for TraceOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accepted => write!(f, "Accepted"),
+            Self::EndedInNonFinalState(state) => {
+                write!(f, "Not accepted: ended in non-final state {}", state)
+            }
+            Self::NoTransition { state, symbol, position } => write!(
+                f,
+                "Not accepted: no transition from {} on {} at position {}",
+                state, symbol, position
+            ),
+        }
+    }
+}
+
+impl StateMachine {
+    /// Runs the state machine on `sequence` and returns every transition taken, along with the
+    /// reason the run ended.
+    pub fn trace<S>(&self, sequence: &[S]) -> Trace
+    where
+        S: AsRef<str>,
+    {
+        let mut steps = Vec::new();
+        let mut state: &str = &self.initial_state;
+
+        for (position, symbol) in sequence.iter().map(|symbol| symbol.as_ref()).enumerate() {
+            match self.next_state(state, symbol) {
+                Some(next_state) => {
+                    steps.push(TraceStep {
+                        state: state.to_owned(),
+                        symbol: symbol.to_owned(),
+                        next_state: next_state.to_owned(),
+                    });
+
+                    state = next_state;
+                }
+                None => {
+                    let outcome = TraceOutcome::NoTransition {
+                        state: state.to_owned(),
+                        symbol: symbol.to_owned(),
+                        position,
+                    };
+
+                    return Trace { steps, outcome };
+                }
+            }
+        }
+
+        let outcome = if self.final_states.contains(state) {
+            TraceOutcome::Accepted
+        } else {
+            TraceOutcome::EndedInNonFinalState(state.to_owned())
+        };
+
+        Trace { steps, outcome }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state_machine::fixtures;
+
+    #[test]
+    fn test_trace() {
+        let state_machine = fixtures::identifier();
+
+        let trace = state_machine.trace(&["_", "a"]);
+        assert!(trace.is_accepted());
+        assert_eq!(trace.steps.len(), 2);
+        assert_eq!(trace.steps[1].state, "underscore-only");
+        assert_eq!(trace.steps[1].next_state, "final");
+
+        let trace = state_machine.trace(&["_"]);
+        assert_eq!(trace.outcome, TraceOutcome::EndedInNonFinalState("underscore-only".to_owned()));
+
+        let trace = state_machine.trace(&["a", "+", "1"]);
+        assert_eq!(trace.steps.len(), 1);
+        assert_eq!(
+            trace.outcome,
+            TraceOutcome::NoTransition {
+                state: "final".to_owned(),
+                symbol: "+".to_owned(),
+                position: 1,
+            },
+        );
+        assert_eq!(
+            trace.to_string(),
+            "initial ---(a)--> final\nNot accepted: no transition from final on + at position 1",
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use crate::state_machine::{
+    SerializedStateMachine, SerializedTrans, StateMachine, StateMachineDeserError, Tag,
+    TraceOutcome,
+};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+
+/// Outputs attached to the states or the transitions of a transducer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TransducerOutputs {
+    /// Moore machine, with an output for each state.
+    Moore(HashMap<String, String>),
+    /// Mealy machine, with an output for each transition, keyed by source state and symbol.
+    Mealy(HashMap<(String, String), String>),
+}
+
+/// State machine that also emits outputs while consuming its input.
+#[derive(Deserialize)]
+#[serde(try_from = "SerializedTransducer")]
+pub struct Transducer {
+    /// The underlying acceptor.
+    state_machine: StateMachine,
+    /// Outputs of the states or the transitions.
+    outputs: TransducerOutputs,
+}
+
+/// Outputs emitted on a sequence, along with the reason the run ended.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Translation {
+    /// Outputs emitted, in order.
+    pub outputs: Vec<String>,
+    /// Reason the run ended.
+    pub outcome: TraceOutcome,
+}
+
+impl Translation {
+    /// Returns whether the translated sequence was accepted.
+    pub fn is_accepted(&self) -> bool {
+        self.outcome == TraceOutcome::Accepted
+    }
+}
+
+impl fmt::Display for Translation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.outputs.join(" "))?;
+        write!(f, "{}", self.outcome)
+    }
+}
+
+impl Transducer {
+    /// Returns the underlying acceptor.
+    pub fn state_machine(&self) -> &StateMachine {
+        &self.state_machine
+    }
+
+    /// Returns the outputs of the states or the transitions.
+    pub fn outputs(&self) -> &TransducerOutputs {
+        &self.outputs
+    }
+
+    /// Runs the transducer on `sequence` and returns the outputs emitted until the end of the
+    /// sequence or until no transition is found.
+    ///
+    /// Moore machines emit the output of the initial state first, then the output of each state
+    /// entered. Mealy machines emit the output of each transition taken.
+    pub fn translate<S>(&self, sequence: &[S]) -> Translation
+    where
+        S: AsRef<str>,
+    {
+        let trace = self.state_machine.trace(sequence);
+
+        let outputs = match &self.outputs {
+            TransducerOutputs::Moore(outputs) => {
+                std::iter::once(self.state_machine.initial_state())
+                    .chain(trace.steps.iter().map(|step| step.next_state.as_str()))
+                    .map(|state| outputs[state].clone())
+                    .collect()
+            }
+            TransducerOutputs::Mealy(outputs) => trace
+                .steps
+                .into_iter()
+                .map(|step| outputs[&(step.state, step.symbol)].clone())
+                .collect(),
+        };
+
+        Translation { outputs, outcome: trace.outcome }
+    }
+
+    /// Serializes the transducer to pretty-printed JSON, in the format of
+    /// [StateMachine::to_json_string].
+    pub fn to_json_string(&self) -> String {
+        let mut output = Vec::new();
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(b"    ");
+        let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+
+        self.serialize(&mut serializer).expect("Failed to serialize transducer");
+        output.push(b'\n');
+
+        String::from_utf8(output).expect("Serialized transducer is not valid UTF-8")
+    }
+}
+
+impl Serialize for Transducer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        SerializedTransducer::from(self).serialize(serializer)
+    }
+}
+
+impl TryFrom<SerializedTransducer> for Transducer {
+    type Error = TransducerError;
+
+    fn try_from(transducer: SerializedTransducer) -> Result<Self, Self::Error> {
+        let mut transition_outputs = HashMap::new();
+        for transition in transducer.transitions.iter() {
+            let output = match &transition.output {
+                Some(output) => output,
+                None => continue,
+            };
+
+            let key = (transition.src_state.clone(), transition.symbol.clone());
+            if let Some(previous) = transition_outputs.insert(key, output.clone()) {
+                if previous != *output {
+                    return Err(TransducerError::ConflictingTransitionOutputs {
+                        src_state: transition.src_state.clone(),
+                        symbol: transition.symbol.clone(),
+                    });
+                }
+            }
+        }
+
+        let state_machine = StateMachine::try_from(SerializedStateMachine {
+            states: transducer.states,
+            alphabet: transducer.alphabet,
+            transitions: transducer
+                .transitions
+                .into_iter()
+                .map(|transition| SerializedTrans {
+                    src_state: transition.src_state,
+                    symbol: transition.symbol,
+                    dst_state: transition.dst_state,
+                })
+                .collect(),
+            initial_state: transducer.initial_state,
+            final_states: transducer.final_states,
+            tags: transducer.tags,
+        })?;
+
+        let outputs = match (transducer.state_outputs.is_empty(), transition_outputs.is_empty()) {
+            (false, false) => return Err(TransducerError::MixedOutputs),
+            (true, true) => return Err(TransducerError::MissingOutputs),
+            (false, true) => {
+                for state in transducer.state_outputs.keys() {
+                    if !state_machine.states.contains(state) {
+                        return Err(StateMachineDeserError::InvalidState(state.clone()).into());
+                    }
+                }
+
+                for state in state_machine.sorted_states() {
+                    if !transducer.state_outputs.contains_key(state) {
+                        return Err(TransducerError::MissingStateOutput(state.to_owned()));
+                    }
+                }
+
+                TransducerOutputs::Moore(transducer.state_outputs.into_iter().collect())
+            }
+            (true, false) => {
+                let mut transitions = state_machine.iter_transitions().collect::<Vec<_>>();
+                transitions.sort_unstable();
+
+                for (src_state, symbol, _) in transitions {
+                    if !transition_outputs.contains_key(&(src_state.to_owned(), symbol.to_owned()))
+                    {
+                        return Err(TransducerError::MissingTransitionOutput {
+                            src_state: src_state.to_owned(),
+                            symbol: symbol.to_owned(),
+                        });
+                    }
+                }
+
+                TransducerOutputs::Mealy(transition_outputs)
+            }
+        };
+
+        Ok(Transducer { state_machine, outputs })
+    }
+}
+
+impl From<&Transducer> for SerializedTransducer {
+    fn from(transducer: &Transducer) -> Self {
+        let machine = SerializedStateMachine::from(&transducer.state_machine);
+
+        let transitions = machine
+            .transitions
+            .into_iter()
+            .map(|transition| {
+                let output = match &transducer.outputs {
+                    TransducerOutputs::Moore(_) => None,
+                    TransducerOutputs::Mealy(outputs) => outputs
+                        .get(&(transition.src_state.clone(), transition.symbol.clone()))
+                        .cloned(),
+                };
+
+                SerializedOutputTrans {
+                    src_state: transition.src_state,
+                    symbol: transition.symbol,
+                    dst_state: transition.dst_state,
+                    output,
+                }
+            })
+            .collect();
+
+        let state_outputs = match &transducer.outputs {
+            TransducerOutputs::Moore(outputs) => {
+                outputs.iter().map(|(state, output)| (state.clone(), output.clone())).collect()
+            }
+            TransducerOutputs::Mealy(_) => BTreeMap::new(),
+        };
+
+        Self {
+            states: machine.states,
+            alphabet: machine.alphabet,
+            transitions,
+            initial_state: machine.initial_state,
+            final_states: machine.final_states,
+            tags: machine.tags,
+            state_outputs,
+        }
+    }
+}
+
+/// Error returned when a transducer cannot be deserialized.
+#[derive(Clone, Debug)]
+pub enum TransducerError {
+    /// The underlying state machine is invalid.
+    StateMachine(StateMachineDeserError),
+    /// Neither the states nor the transitions have outputs.
+    MissingOutputs,
+    /// Both the states and the transitions have outputs.
+    MixedOutputs,
+    /// The given state of a Moore machine has no output.
+    MissingStateOutput(String),
+    /// The given transition of a Mealy machine has no output.
+    MissingTransitionOutput {
+        /// Source state of the transition.
+        src_state: String,
+        /// Symbol of the transition.
+        symbol: String,
+    },
+    /// The given transition of a Mealy machine is listed with different outputs.
+    ConflictingTransitionOutputs {
+        /// Source state of the transition.
+        src_state: String,
+        /// Symbol of the transition.
+        symbol: String,
+    },
+}
+
+impl From<StateMachineDeserError> for TransducerError {
+    fn from(error: StateMachineDeserError) -> Self {
+        Self::StateMachine(error)
+    }
+}
+
+impl Error for TransducerError {}
+
+impl fmt::Display for TransducerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StateMachine(error) => write!(f, "{}", error),
+            Self::MissingOutputs => {
+                write!(f, "Neither the states nor the transitions have outputs")
+            }
+            Self::MixedOutputs => write!(f, "Both the states and the transitions have outputs"),
+            Self::MissingStateOutput(state) => write!(f, "Missing output for state \"{}\"", state),
+            Self::MissingTransitionOutput { src_state, symbol } => write!(
+                f,
+                "Missing output for the transition from \"{}\" on \"{}\"",
+                src_state, symbol
+            ),
+            Self::ConflictingTransitionOutputs { src_state, symbol } => write!(
+                f,
+                "Conflicting outputs for the transition from \"{}\" on \"{}\"",
+                src_state, symbol
+            ),
+        }
+    }
+}
+
+/// Serialized transition of a transducer, with the output of Mealy machines.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize, Serialize)]
+struct SerializedOutputTrans {
+    src_state: String,
+    symbol: String,
+    dst_state: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    output: Option<String>,
+}
+
+/// Serialized form of a transducer: a serialized state machine extended with the outputs of
+/// the states (Moore machines) or of the transitions (Mealy machines).
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct SerializedTransducer {
+    states: BTreeSet<String>,
+    alphabet: BTreeSet<String>,
+    transitions: BTreeSet<SerializedOutputTrans>,
+    initial_state: String,
+    final_states: BTreeSet<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    tags: BTreeMap<String, Tag>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    state_outputs: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moore() {
+        let transducer = serde_json::from_str::<Transducer>(include_str!(
+            "../../../state-machines/number-category.json"
+        ))
+        .unwrap();
+
+        let translation = transducer.translate(&["1", "0"]);
+        assert_eq!(translation.outputs, ["invalid", "number", "number"]);
+        assert!(translation.is_accepted());
+
+        let translation = transducer.translate(&["0"]);
+        assert_eq!(translation.outputs, ["invalid"]);
+        assert!(!translation.is_accepted());
+
+        let round_trip = serde_json::from_str::<Transducer>(&transducer.to_json_string()).unwrap();
+        assert_eq!(round_trip.outputs(), transducer.outputs());
+        assert_eq!(round_trip.state_machine().equivalent(transducer.state_machine()), Ok(()));
+    }
+
+    #[test]
+    fn test_mealy() {
+        // Doubles every `a` and drops every `b`.
+        let json = r#"{
+            "states": ["q"],
+            "alphabet": ["a", "b"],
+            "transitions": [
+                { "src_state": "q", "symbol": "a", "dst_state": "q", "output": "aa" },
+                { "src_state": "q", "symbol": "b", "dst_state": "q", "output": "" }
+            ],
+            "initial_state": "q",
+            "final_states": ["q"]
+        }"#;
+
+        let transducer = serde_json::from_str::<Transducer>(json).unwrap();
+        let translation = transducer.translate(&["a", "b", "a"]);
+        assert_eq!(translation.outputs.concat(), "aaaa");
+        assert_eq!(translation.to_string(), "aa  aa\nAccepted");
+
+        let serialized = transducer.to_json_string();
+        assert!(serialized.contains("\"output\": \"aa\""));
+        assert!(!serialized.contains("state_outputs"));
+    }
+
+    #[test]
+    fn test_invalid_outputs() {
+        let machine = r#""states": ["p", "q"], "alphabet": ["a"], "initial_state": "p",
+            "final_states": ["q"]"#;
+
+        let missing_outputs = format!(
+            r#"{{ {}, "transitions": [{{ "src_state": "p", "symbol": "a", "dst_state": "q" }}] }}"#,
+            machine
+        );
+        let missing_state_output =
+            format!(r#"{{ {}, "transitions": [], "state_outputs": {{ "p": "x" }} }}"#, machine);
+        let mixed_outputs = format!(
+            r#"{{ {}, "transitions": [{{ "src_state": "p", "symbol": "a", "dst_state": "q",
+                "output": "x" }}], "state_outputs": {{ "p": "x", "q": "y" }} }}"#,
+            machine
+        );
+
+        let conflicting_outputs = format!(
+            r#"{{ {}, "transitions": [{{ "src_state": "q", "symbol": "a", "dst_state": "q",
+                "output": "x" }}, {{ "src_state": "q", "symbol": "a", "dst_state": "q",
+                "output": "y" }}, {{ "src_state": "p", "symbol": "a", "dst_state": "q",
+                "output": "x" }}] }}"#,
+            machine
+        );
+
+        for json in [missing_outputs, missing_state_output, mixed_outputs, conflicting_outputs] {
+            assert!(serde_json::from_str::<Transducer>(&json).is_err(), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Hashes the given string.
+pub fn hash_str(value: &str) -> u64 {
+    value.chars().fold(7_u64, |hash, c| hash.wrapping_mul(31).wrapping_add(u64::from(c)))
+}
+
+/// Returns whether `n` is prime.
+// `usize::is_multiple_of` needs Rust 1.87, which the crate does not require.
+#[allow(clippy::manual_is_multiple_of)]
+pub fn is_prime(n: usize) -> bool {
+    if n <= 1 {
+        false
+    } else if n <= 3 {
+        true
+    } else if n % 2 == 0 {
+        false
+    } else {
+        let mut i = 3;
+
+        while i * i <= n {
+            if n % i == 0 {
+                return false;
+            }
+
+            i += 2;
+        }
+
+        true
+    }
+}
+
+/// Returns the next prime number greater than or equal to `n`.
+pub fn next_prime(n: usize) -> usize {
+    for i in n..usize::MAX {
+        if is_prime(i) {
+            return i;
+        }
+    }
+
+    panic!("Prime number does not fit u64");
+}
+
+pub fn str_to_grapheme_clusters(input: &str) -> Vec<&str> {
+    input.graphemes(true).collect::<Vec<_>>()
+}
+
+/// Seedable pseudo-random number generator based on the SplitMix64 algorithm.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a new generator from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Creates a new generator seeded from the system clock.
+    pub fn from_time() -> Self {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_nanos() as u64)
+            .unwrap_or_default();
+
+        Self::new(seed)
+    }
+
+    /// Returns the next random 64-bit number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a uniformly distributed random number in `0..bound`.
+    pub fn below(&mut self, bound: u128) -> u128 {
+        assert!(bound != 0, "Bound must be greater than zero");
+
+        // Reject values from the incomplete last interval to avoid modulo bias.
+        let limit = u128::MAX - u128::MAX % bound;
+
+        loop {
+            let value = (u128::from(self.next_u64()) << 64) | u128::from(self.next_u64());
+
+            if value < limit {
+                return value % bound;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_prime() {
+        assert!(is_prime(2));
+        assert!(is_prime(5));
+        assert!(is_prime(7));
+
+        assert!(!is_prime(0));
+        assert!(!is_prime(1));
+        assert!(!is_prime(4));
+    }
+
+    #[test]
+    fn test_next_prime() {
+        assert_eq!(next_prime(2), 2);
+        assert_eq!(next_prime(4), 5);
+        assert_eq!(next_prime(5), 5);
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(42);
+        let values = (0..100).map(|_| rng.below(10)).collect::<Vec<_>>();
+
+        assert!(values.iter().all(|&value| value < 10));
+        assert!(values.iter().any(|&value| value != values[0]));
+
+        let mut same_rng = Rng::new(42);
+        assert!(values.iter().all(|&value| value == same_rng.below(10)));
+    }
+}
@@ -0,0 +1,33 @@
+use compiler::state_machine::StateMachine;
+use compiler::utils;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+/// Compares running the hash map based state machines against their compiled form, on the
+/// words the scanner checks.
+fn bench_is_accepted(c: &mut Criterion) {
+    let machines = [
+        ("number", include_str!("../state-machines/number.json"), "1202210201221002"),
+        ("identifier", include_str!("../state-machines/identifier.json"), "_a1_a1aa11__a1a_"),
+    ];
+
+    for (name, json, word) in machines {
+        let state_machine = serde_json::from_str::<StateMachine>(json).unwrap();
+        let compiled = state_machine.compile();
+        let sequence = utils::str_to_grapheme_clusters(word);
+
+        c.bench_function(&format!("{}/hash_map", name), |b| {
+            b.iter(|| state_machine.is_accepted(black_box(&sequence)))
+        });
+
+        c.bench_function(&format!("{}/compiled", name), |b| {
+            b.iter(|| compiled.is_accepted(black_box(&sequence)))
+        });
+
+        c.bench_function(&format!("{}/compiled_str", name), |b| {
+            b.iter(|| compiled.accepts(black_box(word)))
+        });
+    }
+}
+
+criterion_group!(benches, bench_is_accepted);
+criterion_main!(benches);
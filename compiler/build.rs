@@ -0,0 +1,29 @@
+//! Compiles the scanner's state machines into Rust modules, so that they are validated at build
+//! time and do not have to be loaded at runtime.
+
+use automata::state_machine::StateMachine;
+use std::path::PathBuf;
+
+/// State machines used by the scanner, as names of files in `state-machines/`.
+const STATE_MACHINES: [&str; 2] = ["number", "identifier"];
+
+fn main() {
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is not set"));
+
+    for name in STATE_MACHINES {
+        let path = format!("state-machines/{}.json", name);
+        println!("cargo:rerun-if-changed={}", path);
+
+        let json = std::fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("Failed to read {}: {}", path, error));
+        let state_machine = serde_json::from_str::<StateMachine>(&json)
+            .unwrap_or_else(|error| panic!("Failed to parse {}: {}", path, error));
+
+        if state_machine.is_empty() {
+            panic!("{} does not accept any sequence", path);
+        }
+
+        std::fs::write(out_dir.join(format!("{}.rs", name)), state_machine.to_rust_module())
+            .unwrap_or_else(|error| panic!("Failed to write the module for {}: {}", path, error));
+    }
+}
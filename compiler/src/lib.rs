@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+/// Grammars in BNF notation, such as those of the language specification.
+pub use automata::grammar;
+
+/// Pretty-print the tokens and symbol tables of a program.
+pub mod output;
+
+/// Predictive parsing of token streams with LL(1) grammars.
+pub mod parser;
+
+/// Scanner for splitting source files into tokens.
+pub mod scanner;
+
+/// Symbols and symbol table for storing constans and identifiers.
+pub mod symbols;
+
+/// Various utility functions.
+pub use automata::utils;
+
+/// State machine implementation.
+pub use automata::state_machine;
@@ -1,23 +1,16 @@
-#![allow(dead_code)]
+/// Interactive session for editing and querying state machines.
+mod repl;
 
-/// Pretty-print the tokens and symbol tables of a program.
-mod output;
-
-/// Scanner for splitting source files into tokens.
-mod scanner;
-
-/// Symbols and symbol table for storing constans and identifiers.
-mod symbols;
-
-/// Various utility functions.
-mod utils;
-
-/// State machine implementation.
-mod state_machine;
-
-use crate::scanner::Program;
-use crate::state_machine::StateMachine;
-use std::io::Write;
+use compiler::grammar::{Grammar, NonTerminal};
+use compiler::output;
+use compiler::parser::Ll1Parser;
+use compiler::scanner::Program;
+use compiler::state_machine::{
+    parse_test_cases, ConfigurationTrace, ExampleOracle, PushdownAutomaton, PushdownOutcome,
+    StateMachine, StateMachineFormat,
+};
+use repl::Repl;
+use std::error::Error;
 use unicode_segmentation::UnicodeSegmentation;
 
 fn run_compiler(args: &[String]) {
@@ -29,7 +22,7 @@ fn run_compiler(args: &[String]) {
         }
     };
 
-    let source = match std::fs::read_to_string(&source_path) {
+    let source = match std::fs::read_to_string(source_path) {
         Ok(source) => source,
         Err(error) => {
             eprintln!("Failed to read source file: {}", error);
@@ -51,6 +44,30 @@ fn run_compiler(args: &[String]) {
 }
 
 fn run_state_machine(args: &[String]) {
+    match args.get(2).map(String::as_str) {
+        Some("diff") => {
+            run_state_machine_diff(args);
+            return;
+        }
+        Some("from-regex") => {
+            run_state_machine_from_regex(args);
+            return;
+        }
+        Some("learn") => {
+            run_state_machine_learn(args);
+            return;
+        }
+        Some("pushdown") => {
+            run_pushdown_automaton(args);
+            return;
+        }
+        Some("test") => {
+            run_state_machine_test(args);
+            return;
+        }
+        _ => (),
+    }
+
     let state_machine_path = match args.get(2) {
         Some(state_machine_path) => state_machine_path,
         None => {
@@ -59,7 +76,7 @@ fn run_state_machine(args: &[String]) {
         }
     };
 
-    let state_machine_string = match std::fs::read_to_string(&state_machine_path) {
+    let state_machine_string = match std::fs::read_to_string(state_machine_path) {
         Ok(state_machine_string) => state_machine_string,
         Err(error) => {
             eprintln!("Failed to read state_machine file: {}", error);
@@ -67,7 +84,9 @@ fn run_state_machine(args: &[String]) {
         }
     };
 
-    let state_machine = match serde_json::from_str::<StateMachine>(&state_machine_string) {
+    let state_machine_format = StateMachineFormat::from_path(state_machine_path);
+
+    let state_machine = match StateMachine::parse(&state_machine_string, state_machine_format) {
         Ok(state_machine) => state_machine,
         Err(error) => {
             eprintln!("Failed to deserialize state_machine: {}", error);
@@ -75,70 +94,410 @@ fn run_state_machine(args: &[String]) {
         }
     };
 
-    let mut input = String::new();
-    let mut should_run = true;
+    let transducer = repl::parse_transducer(&state_machine_string, state_machine_format);
 
-    while should_run {
-        print!(">>> ");
-        std::io::stdout().flush().expect("Failed to flush stdout");
+    match args.get(3).map(String::as_str) {
+        Some("--emit") => match args.get(4).map(String::as_str) {
+            Some("dot") => {
+                print!("{}", state_machine.to_dot());
+                return;
+            }
+            Some("json") => {
+                print!("{}", state_machine.to_json_string());
+                return;
+            }
+            Some("fa") => {
+                print!("{}", state_machine.to_fa_string());
+                return;
+            }
+            Some("rust") => {
+                print!("{}", state_machine.to_rust_module());
+                return;
+            }
+            Some(unknown) => {
+                eprintln!("Unknown emit format: '{}'", unknown);
+                std::process::exit(6);
+            }
+            None => {
+                eprintln!("No emit format provided");
+                std::process::exit(6);
+            }
+        },
+        Some(unknown) => {
+            eprintln!("Unknown option: '{}'", unknown);
+            std::process::exit(6);
+        }
+        None => (),
+    }
 
-        input.clear();
-        std::io::stdin().read_line(&mut input).expect("Failed to read from stdin");
-        let mut words = input.split_whitespace().map(str::trim);
+    Repl::new(state_machine, transducer).run();
+}
 
-        if let Some(command) = words.next() {
-            match command {
-                "display" => {
-                    display_state_machine(&state_machine);
-                }
-                "validate" => match words.next() {
-                    Some(sequence) => {
-                        let split_sequence = sequence.graphemes(true).collect::<Vec<_>>();
-
-                        if state_machine.is_accepted(&split_sequence) {
-                            println!("{} is accepted", sequence);
-                        } else {
-                            println!("{} is not accepted", sequence);
-                        }
-                    }
-                    None => eprintln!("No sequence provided"),
-                },
-                "exit" => {
-                    should_run = false;
+fn run_state_machine_diff(args: &[String]) {
+    let (first_path, second_path) = match (args.get(3), args.get(4)) {
+        (Some(first_path), Some(second_path)) => (first_path, second_path),
+        _ => {
+            eprintln!("Two state_machine files must be provided");
+            std::process::exit(3);
+        }
+    };
+
+    let load = |path: &str| match read_state_machine(path) {
+        Ok(state_machine) => state_machine,
+        Err(error) => {
+            eprintln!("Failed to load state_machine {}: {}", path, error);
+            std::process::exit(4);
+        }
+    };
+
+    let first = load(first_path);
+    let second = load(second_path);
+
+    match first.equivalent(&second) {
+        Ok(()) => println!("{} and {} are equivalent", first_path, second_path),
+        Err(counterexample) => {
+            let (accepting_path, rejecting_path) = if counterexample.accepted_by_self {
+                (first_path, second_path)
+            } else {
+                (second_path, first_path)
+            };
+
+            println!(
+                "\"{}\" is accepted by {}, but not by {}",
+                counterexample.sequence.concat(),
+                accepting_path,
+                rejecting_path
+            );
+            std::process::exit(7);
+        }
+    }
+}
+
+fn run_state_machine_test(args: &[String]) {
+    let (state_machine_path, cases_path) = match (args.get(3), args.get(4)) {
+        (Some(state_machine_path), Some(cases_path)) => (state_machine_path, cases_path),
+        _ => {
+            eprintln!("A state_machine file and a cases file must be provided");
+            std::process::exit(3);
+        }
+    };
+
+    let state_machine = match read_state_machine(state_machine_path) {
+        Ok(state_machine) => state_machine,
+        Err(error) => {
+            eprintln!("Failed to load state_machine {}: {}", state_machine_path, error);
+            std::process::exit(4);
+        }
+    };
+
+    let cases_string = match std::fs::read_to_string(cases_path) {
+        Ok(cases_string) => cases_string,
+        Err(error) => {
+            eprintln!("Failed to read cases file: {}", error);
+            std::process::exit(4);
+        }
+    };
+
+    let cases = match parse_test_cases(&cases_string) {
+        Ok(cases) => cases,
+        Err(error) => {
+            eprintln!("Failed to parse cases file: {}", error);
+            std::process::exit(5);
+        }
+    };
+
+    let report = state_machine.run_test_cases(&cases);
+    println!("{}", report);
+
+    if !report.is_success() {
+        std::process::exit(8);
+    }
+}
+
+fn run_state_machine_learn(args: &[String]) {
+    let learned = match (args.get(3).map(String::as_str), args.get(4)) {
+        (Some("--examples"), Some(examples_path)) => {
+            let examples_string = match std::fs::read_to_string(examples_path) {
+                Ok(examples_string) => examples_string,
+                Err(error) => {
+                    eprintln!("Failed to read examples file: {}", error);
+                    std::process::exit(4);
                 }
-                unknown => {
-                    eprintln!("Unknown command: '{}'", unknown);
+            };
+
+            let oracle = match parse_test_cases(&examples_string)
+                .and_then(|cases| ExampleOracle::new(&cases))
+            {
+                Ok(oracle) => oracle,
+                Err(error) => {
+                    eprintln!("Failed to parse examples file: {}", error);
+                    std::process::exit(5);
                 }
+            };
+
+            StateMachine::learn(&oracle)
+        }
+        (Some("--examples"), None) => {
+            eprintln!("No examples file provided");
+            std::process::exit(3);
+        }
+        (Some(state_machine_path), _) => match read_state_machine(state_machine_path) {
+            Ok(state_machine) => StateMachine::learn(&state_machine),
+            Err(error) => {
+                eprintln!("Failed to load state_machine {}: {}", state_machine_path, error);
+                std::process::exit(4);
             }
+        },
+        (None, _) => {
+            eprintln!("A state_machine file or an examples file must be provided");
+            std::process::exit(3);
         }
+    };
 
-        println!();
+    eprintln!(
+        "Learned {} states with {} membership and {} equivalence queries",
+        learned.state_machine.iter_states().count(),
+        learned.membership_queries,
+        learned.equivalence_queries
+    );
+    print!("{}", learned.state_machine.to_json_string());
+}
+
+fn run_state_machine_from_regex(args: &[String]) {
+    let pattern = match args.get(3) {
+        Some(pattern) => pattern,
+        None => {
+            eprintln!("No regular expression provided");
+            std::process::exit(3);
+        }
+    };
+
+    match StateMachine::from_regexp(pattern) {
+        Ok(state_machine) => print!("{}", state_machine.to_json_string()),
+        Err(error) => {
+            eprintln!("Failed to parse regular expression: {}", error);
+            std::process::exit(5);
+        }
     }
 }
 
-fn display_state_machine(state_machine: &StateMachine) {
-    println!("[STATES]");
-    for state in state_machine.iter_states() {
-        println!("{}", state);
+fn run_pushdown_automaton(args: &[String]) {
+    let (automaton_path, sequence) = match (args.get(3), args.get(4)) {
+        (Some(automaton_path), Some(sequence)) => (automaton_path, sequence),
+        _ => {
+            eprintln!("A pushdown automaton file and a sequence must be provided");
+            std::process::exit(3);
+        }
+    };
+
+    let max_steps = match args.get(5).map(String::as_str) {
+        Some("--max-steps") => match args.get(6).map(|steps| steps.parse::<usize>()) {
+            Some(Ok(max_steps)) => max_steps,
+            Some(Err(error)) => {
+                eprintln!("Invalid step bound: {}", error);
+                std::process::exit(6);
+            }
+            None => {
+                eprintln!("No step bound provided");
+                std::process::exit(6);
+            }
+        },
+        Some(unknown) => {
+            eprintln!("Unknown option: '{}'", unknown);
+            std::process::exit(6);
+        }
+        None => 10_000,
+    };
+
+    let automaton_string = match std::fs::read_to_string(automaton_path) {
+        Ok(automaton_string) => automaton_string,
+        Err(error) => {
+            eprintln!("Failed to read pushdown automaton file: {}", error);
+            std::process::exit(4);
+        }
+    };
+
+    let automaton = match serde_json::from_str::<PushdownAutomaton>(&automaton_string) {
+        Ok(automaton) => automaton,
+        Err(error) => {
+            eprintln!("Failed to deserialize pushdown automaton: {}", error);
+            std::process::exit(5);
+        }
+    };
+
+    let split_sequence = sequence.graphemes(true).collect::<Vec<_>>();
+
+    match automaton.run(&split_sequence, max_steps) {
+        PushdownOutcome::Accepted(configurations) => {
+            let trace =
+                ConfigurationTrace { configurations: &configurations, input: &split_sequence };
+            println!("{}", trace);
+            println!("{} is accepted", sequence);
+        }
+        PushdownOutcome::Rejected => println!("{} is not accepted", sequence),
+        PushdownOutcome::StepLimitReached => {
+            println!("No accepting run found for {} within {} steps", sequence, max_steps)
+        }
     }
+}
+
+fn run_grammar(args: &[String]) {
+    let command = match args.get(2) {
+        Some(command) => command.as_str(),
+        None => {
+            eprintln!("No grammar command provided");
+            std::process::exit(3);
+        }
+    };
+
+    let grammar_path = match args.get(3) {
+        Some(grammar_path) => grammar_path,
+        None => {
+            eprintln!("No grammar file provided");
+            std::process::exit(3);
+        }
+    };
+
+    let grammar_string = match std::fs::read_to_string(grammar_path) {
+        Ok(grammar_string) => grammar_string,
+        Err(error) => {
+            eprintln!("Failed to read grammar file: {}", error);
+            std::process::exit(4);
+        }
+    };
+
+    let grammar = match Grammar::parse(&grammar_string) {
+        Ok(grammar) => grammar,
+        Err(error) => {
+            eprintln!("Failed to parse grammar: {}", error);
+            std::process::exit(5);
+        }
+    };
+
+    match command {
+        "info" => display_grammar(&grammar),
+        "first-follow" => match grammar.first_follow() {
+            Ok(sets) => print!("{}", sets),
+            Err(error) => {
+                eprintln!("Failed to analyze grammar: {}", error);
+                std::process::exit(5);
+            }
+        },
+        "ll1" => match grammar.ll1_table() {
+            Ok(table) => {
+                print!("{}", table);
 
-    println!("\n[ALPHABET]");
-    for symbol in state_machine.iter_symbols() {
-        println!("{}", symbol);
+                if !table.is_ll1() {
+                    std::process::exit(9);
+                }
+            }
+            Err(error) => {
+                eprintln!("Failed to analyze grammar: {}", error);
+                std::process::exit(5);
+            }
+        },
+        "parse" => parse_program(&grammar, args),
+        "productions" => match args.get(4) {
+            Some(name) => {
+                let nonterminal = NonTerminal(name.clone());
+
+                for (i, production) in grammar.productions_for(&nonterminal) {
+                    println!("{}: {}", i + 1, production);
+                }
+            }
+            None => {
+                eprintln!("No nonterminal provided");
+                std::process::exit(3);
+            }
+        },
+        unknown => {
+            eprintln!("Unknown grammar command: '{}'", unknown);
+            std::process::exit(2);
+        }
     }
+}
+
+fn parse_program(grammar: &Grammar, args: &[String]) {
+    let parser = match grammar.ll1_table().map(Ll1Parser::new) {
+        Ok(Ok(parser)) => parser,
+        Ok(Err(error)) => {
+            eprintln!("{}", error);
+            std::process::exit(9);
+        }
+        Err(error) => {
+            eprintln!("Failed to analyze grammar: {}", error);
+            std::process::exit(5);
+        }
+    };
+
+    let source_path = match args.get(4) {
+        Some(source_path) => source_path,
+        None => {
+            eprintln!("No source file provided");
+            std::process::exit(3);
+        }
+    };
+
+    let source = match std::fs::read_to_string(source_path) {
+        Ok(source) => source,
+        Err(error) => {
+            eprintln!("Failed to read source file: {}", error);
+            std::process::exit(4);
+        }
+    };
+
+    let program = match Program::from_source(&source) {
+        Ok(program) => program,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(5);
+        }
+    };
+
+    let parse = match parser.parse_program(&program) {
+        Ok(parse) => parse,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(5);
+        }
+    };
+
+    let derivation = parse.derivation.iter().map(|i| (i + 1).to_string()).collect::<Vec<_>>();
+
+    println!("[DERIVATION]");
+    println!("{}", derivation.join(" "));
+
+    println!("\n[PARSE TREE]");
+    print!("{}", parse.tree);
+}
 
-    println!("\n[TRANSITIONS]");
-    for (src_state, symbol, dst_state) in state_machine.iter_transitions() {
-        println!("{} ---({})--> {}", src_state, symbol, dst_state);
+fn display_grammar(grammar: &Grammar) {
+    println!("[START SYMBOL]");
+    println!("{}", grammar.start_symbol());
+
+    println!("\n[NONTERMINALS]");
+    for nonterminal in grammar.nonterminals() {
+        println!("{}", nonterminal);
     }
 
-    println!("\n[INITIAL STATE]");
-    println!("{}", state_machine.initial_state());
+    println!("\n[TERMINALS]");
+    for terminal in grammar.terminals() {
+        println!("{}", terminal);
+    }
 
-    println!("\n[FINAL STATES]");
-    for final_state in state_machine.final_states() {
-        println!("{}", final_state);
+    println!("\n[PRODUCTIONS]");
+    for (i, production) in grammar.productions().iter().enumerate() {
+        println!("{}: {}", i + 1, production);
     }
+
+    println!("\n[CLASS]");
+    println!("Context-free: {}", grammar.is_context_free());
+    println!("Right-linear: {}", grammar.is_right_linear());
+}
+
+fn read_state_machine(path: &str) -> Result<StateMachine, Box<dyn Error>> {
+    let state_machine_string = std::fs::read_to_string(path)?;
+    StateMachine::parse(&state_machine_string, StateMachineFormat::from_path(path))
 }
 
 fn main() {
@@ -148,6 +507,7 @@ fn main() {
         match run_mode {
             "compile" => run_compiler(&args),
             "state_machine" => run_state_machine(&args),
+            "grammar" => run_grammar(&args),
             unknown => {
                 eprintln!("Unknown run mode: '{}'", unknown);
                 std::process::exit(2);
@@ -0,0 +1,55 @@
+use crate::grammar::Lookahead;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned by the parser at the first token that cannot be matched.
+#[derive(Clone, Debug)]
+pub struct SyntaxError {
+    /// Text of the unexpected token, or [None] at the end of the input.
+    found: Option<String>,
+    /// Index of the unexpected token in the input.
+    position: usize,
+    /// Lookaheads that would have been accepted instead.
+    expected: Vec<Lookahead>,
+}
+
+impl SyntaxError {
+    /// Creates a new error.
+    pub fn new(found: Option<String>, position: usize, expected: Vec<Lookahead>) -> Self {
+        Self { found, position, expected }
+    }
+
+    /// Returns the text of the unexpected token, or [None] at the end of the input.
+    pub fn found(&self) -> Option<&str> {
+        self.found.as_deref()
+    }
+
+    /// Returns the index of the unexpected token in the input.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the lookaheads that would have been accepted instead.
+    pub fn expected(&self) -> &[Lookahead] {
+        &self.expected
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.found.as_ref() {
+            Some(found) => write!(f, "Unexpected \"{}\" (token {})", found, self.position)?,
+            None => write!(f, "Unexpected end of input")?,
+        }
+
+        // Commas cannot separate the lookaheads, as `,` is a terminal too.
+        write!(f, ", expected:")?;
+        for lookahead in self.expected.iter() {
+            write!(f, " {}", lookahead)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for SyntaxError {}
@@ -0,0 +1,106 @@
+use crate::grammar::Terminal;
+use crate::scanner::{Program, ReservedWord, Token};
+use crate::symbols::{Const, Symbol};
+
+/// Token as seen by the parser: its text and the grammar terminals it may stand for, most
+/// specific first. The parser matches the first of them the grammar accepts, so `10` can be an
+/// `<int_lit>` in an array type and a `<primitive_lit>` in an expression.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Lexeme {
+    /// Text of the token in the source.
+    pub text: String,
+    /// Terminals the token may stand for, most specific first.
+    pub terminals: Vec<Terminal>,
+}
+
+impl Lexeme {
+    /// Creates a new lexeme.
+    pub fn new<S>(text: S, terminals: Vec<Terminal>) -> Self
+    where
+        S: Into<String>,
+    {
+        Self { text: text.into(), terminals }
+    }
+
+    /// Creates a lexeme standing only for the literal terminal matching its text.
+    pub fn literal<S>(text: S) -> Self
+    where
+        S: Into<String>,
+    {
+        let text = text.into();
+        let terminals = vec![Terminal::Literal(text.clone())];
+        Self { text, terminals }
+    }
+
+    /// Maps a token of `program` to the terminals of `specs/syntax.in`:
+    ///
+    /// - separators and control flow words stand for their text;
+    /// - operators stand for their text, then `<op>`;
+    /// - primitive types stand for their text, then `<primitive>`;
+    /// - `true` and `false` stand for their text, then `<primitive_lit>`;
+    /// - integer literals stand for `<int_lit>`, then `<primitive_lit>`;
+    /// - other literals stand for `<primitive_lit>`;
+    /// - identifiers stand for `<ident>`.
+    ///
+    /// `idents` and `consts` hold the symbols of the program's tables indexed by code, as
+    /// returned by [crate::symbols::SymbolTable::symbols_by_code].
+    pub fn from_token(
+        token: &Token,
+        idents: &[Option<&Symbol>],
+        consts: &[Option<&Symbol>],
+    ) -> Self {
+        let with_token = |text: &str, name: &str| {
+            Self::new(
+                text,
+                vec![Terminal::Literal(text.to_owned()), Terminal::Token(name.to_owned())],
+            )
+        };
+
+        match token {
+            Token::Separator(separator) => Self::literal(separator.as_str()),
+            Token::Operator(operator) => with_token(operator.as_str(), "op"),
+            Token::ReservedWord(ReservedWord::Primitive(primitive)) => {
+                with_token(primitive.as_str(), "primitive")
+            }
+            Token::ReservedWord(ReservedWord::ControlFlow(control_flow)) => {
+                Self::literal(control_flow.as_str())
+            }
+            Token::ReservedWord(ReservedWord::BoolLiteral(bool_literal)) => {
+                with_token(bool_literal.as_str(), "primitive_lit")
+            }
+            Token::Literal(code) => {
+                let primitive_lit = Terminal::Token("primitive_lit".to_owned());
+
+                match consts.get(*code).copied().flatten() {
+                    Some(Symbol::Const(Const::I32(value))) => Self::new(
+                        value.to_string(),
+                        vec![Terminal::Token("int_lit".to_owned()), primitive_lit],
+                    ),
+                    Some(Symbol::Const(Const::Char(value))) => {
+                        Self::new(format!("'{}'", value), vec![primitive_lit])
+                    }
+                    Some(Symbol::Const(Const::Str(value))) => {
+                        Self::new(format!("\"{}\"", value), vec![primitive_lit])
+                    }
+                    _ => Self::new(format!("#{}", code), vec![primitive_lit]),
+                }
+            }
+            Token::Ident(code) => {
+                let text = match idents.get(*code).copied().flatten() {
+                    Some(Symbol::Ident(name)) => name.clone(),
+                    _ => format!("#{}", code),
+                };
+
+                Self::new(text, vec![Terminal::Token("ident".to_owned())])
+            }
+        }
+    }
+
+    /// Maps all tokens of `program` to lexemes, in order.
+    pub fn from_program(program: &Program) -> Vec<Self> {
+        let idents = program.idents().symbols_by_code();
+        let consts = program.consts().symbols_by_code();
+
+        program.tokens().iter().map(|token| Self::from_token(token, &idents, &consts)).collect()
+    }
+}
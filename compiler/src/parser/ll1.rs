@@ -0,0 +1,206 @@
+use crate::grammar::{GrammarError, Ll1Table, Lookahead, Symbol};
+use crate::parser::{Lexeme, ParseTree, SyntaxError};
+use crate::scanner::Program;
+
+/// Result of a successful parse.
+#[derive(Clone, Debug)]
+pub struct Parse {
+    /// Leftmost derivation of the input, as indices in [crate::grammar::Grammar::productions].
+    pub derivation: Vec<usize>,
+    /// Parse tree of the input.
+    pub tree: ParseTree,
+}
+
+/// Predictive parser driven by the LL(1) table of a grammar.
+#[derive(Clone, Debug)]
+pub struct Ll1Parser {
+    /// The table, which has no conflicts.
+    table: Ll1Table,
+}
+
+impl Ll1Parser {
+    /// Creates a parser from an LL(1) table. Returns an error if the table has conflicts.
+    pub fn new(table: Ll1Table) -> Result<Self, GrammarError> {
+        let conflicts = table.conflicts();
+
+        if !conflicts.is_empty() {
+            return Err(GrammarError::new(
+                format!("The grammar is not LL(1): {} cells have conflicts", conflicts.len()),
+                0,
+            ));
+        }
+
+        Ok(Self { table })
+    }
+
+    /// Returns the table driving the parser.
+    pub fn table(&self) -> &Ll1Table {
+        &self.table
+    }
+
+    /// Parses the tokens of `program`.
+    pub fn parse_program(&self, program: &Program) -> Result<Parse, SyntaxError> {
+        self.parse(&Lexeme::from_program(program))
+    }
+
+    /// Parses `lexemes` from the start symbol. The nonterminal on top of the stack is expanded
+    /// with the production of the first terminal of the next lexeme that has a table entry,
+    /// while terminals on top of the stack must be among those of the next lexeme. Stops at the
+    /// first lexeme that cannot be matched.
+    pub fn parse(&self, lexemes: &[Lexeme]) -> Result<Parse, SyntaxError> {
+        let productions = self.table.productions();
+        let mut stack = vec![Symbol::NonTerminal(self.table.start_symbol().clone())];
+        let mut derivation = Vec::new();
+        let mut position = 0;
+
+        let error = |position: usize, expected: Vec<Lookahead>| {
+            let found = lexemes.get(position).map(|lexeme| lexeme.text.clone());
+            SyntaxError::new(found, position, expected)
+        };
+
+        while let Some(symbol) = stack.pop() {
+            let lexeme = lexemes.get(position);
+
+            match symbol {
+                Symbol::Terminal(terminal) => {
+                    if !lexeme.is_some_and(|lexeme| lexeme.terminals.contains(&terminal)) {
+                        return Err(error(position, vec![Lookahead::Terminal(terminal)]));
+                    }
+
+                    position += 1;
+                }
+                Symbol::NonTerminal(nonterminal) => {
+                    let production = match lexeme {
+                        Some(lexeme) => lexeme.terminals.iter().find_map(|terminal| {
+                            let lookahead = Lookahead::Terminal(terminal.clone());
+                            self.table.production(&nonterminal, &lookahead)
+                        }),
+                        None => self.table.production(&nonterminal, &Lookahead::End),
+                    };
+
+                    let production = match production {
+                        Some(production) => production,
+                        None => {
+                            let expected = self.table.expected(&nonterminal);
+                            return Err(error(position, expected.into_iter().cloned().collect()));
+                        }
+                    };
+
+                    derivation.push(production);
+                    stack.extend(productions[production].rhs.iter().rev().cloned());
+                }
+            }
+        }
+
+        if position < lexemes.len() {
+            return Err(error(position, vec![Lookahead::End]));
+        }
+
+        let tree = ParseTree::from_derivation(productions, &derivation, lexemes)
+            .expect("the derivation matches the lexemes");
+
+        Ok(Parse { derivation, tree })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::{Grammar, Terminal};
+
+    /// Expression grammar from the Dragon Book, shared with the grammar tests.
+    const EXPRESSIONS: &str = include_str!("../../grammars/expressions.in");
+
+    /// LL(1) grammar for the statements of the language that the scanner can split.
+    const STATEMENTS: &str = "\
+        <program> ::= <stmt_list>\n\
+        <stmt_list> ::= <stmt> <stmt_list> | (nothing)\n\
+        <stmt> ::= <ident> <assign>\n\
+                 | if <expr> { <stmt_list> } <else>\n\
+                 | while <expr> { <stmt_list> }\n\
+        <assign> ::= = <expr> ; | : <type> = <expr> ;\n\
+        <else> ::= else { <stmt_list> } | (nothing)\n\
+        <type> ::= <primitive> | [ <int_lit> ] <type>\n\
+        <expr> ::= <operand> <expr_rest>\n\
+        <expr_rest> ::= <op> <operand> <expr_rest> | (nothing)\n\
+        <operand> ::= <ident> | <primitive_lit>\n";
+
+    fn parser(grammar: &str) -> Ll1Parser {
+        Ll1Parser::new(Grammar::parse(grammar).unwrap().ll1_table().unwrap()).unwrap()
+    }
+
+    fn lexemes(input: &str) -> Vec<Lexeme> {
+        input.split_whitespace().map(Lexeme::literal).collect()
+    }
+
+    #[test]
+    fn test_expression_grammar() {
+        let parser = parser(EXPRESSIONS);
+
+        let parse = parser.parse(&lexemes("id + id * id")).unwrap();
+        assert_eq!(parse.derivation, [0, 3, 7, 5, 1, 3, 7, 4, 7, 5, 2]);
+        assert_eq!(parse.tree.leaves(), ["id", "+", "id", "*", "id"]);
+
+        let error = parser.parse(&lexemes("id + * id")).unwrap_err();
+        assert_eq!(error.found(), Some("*"));
+        assert_eq!(error.position(), 2);
+        assert_eq!(error.to_string(), "Unexpected \"*\" (token 2), expected: ( id");
+
+        let error = parser.parse(&lexemes("( id")).unwrap_err();
+        assert_eq!(error.found(), None);
+        assert_eq!(error.to_string(), "Unexpected end of input, expected: )");
+
+        let error = parser.parse(&lexemes("id )")).unwrap_err();
+        assert_eq!(error.position(), 1);
+        assert_eq!(error.expected(), [Lookahead::End]);
+    }
+
+    #[test]
+    fn test_program() {
+        let parser = parser(STATEMENTS);
+        let source = "a: [2]i32 = 10;\nwhile a > 1 {\n    a = a - 1;\n}\n";
+        let program = Program::from_source(source).unwrap();
+        let parse = parser.parse_program(&program).unwrap();
+
+        let text = source.split_whitespace().collect::<String>();
+        assert_eq!(parse.tree.leaves().concat(), text);
+        assert_eq!(parse.derivation[0], 0);
+
+        match &parse.tree {
+            ParseTree::Node { nonterminal, children, .. } => {
+                assert_eq!(nonterminal.name(), "program");
+                assert_eq!(children.len(), 1);
+            }
+            ParseTree::Leaf { .. } => panic!("the root is a leaf"),
+        }
+
+        // `2` is matched as an <int_lit> in the type and `10` as a <primitive_lit>.
+        let tree = parse.tree.to_string();
+        assert!(tree.contains("<int_lit> 2\n"));
+        assert!(tree.contains("<primitive_lit> 10\n"));
+        assert!(tree.contains("<op> >\n"));
+    }
+
+    #[test]
+    fn test_program_error() {
+        let parser = parser(STATEMENTS);
+        let program = Program::from_source("a = ;\n").unwrap();
+        let error = parser.parse_program(&program).unwrap_err();
+
+        assert_eq!(error.position(), 2);
+        assert_eq!(error.found(), Some(";"));
+        assert_eq!(
+            error.expected(),
+            [
+                Lookahead::Terminal(Terminal::Token("ident".to_owned())),
+                Lookahead::Terminal(Terminal::Token("primitive_lit".to_owned())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_ll1() {
+        let grammar = Grammar::parse(include_str!("../../../specs/syntax.in")).unwrap();
+        assert!(Ll1Parser::new(grammar.ll1_table().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,16 @@
+/// Error returned when the input does not match the grammar.
+mod error;
+
+/// Tokens as seen by the parser, with the grammar terminals they may stand for.
+mod lexeme;
+
+/// Table-driven predictive parser for LL(1) grammars.
+mod ll1;
+
+/// Parse trees built from leftmost derivations.
+mod tree;
+
+pub use self::error::*;
+pub use self::lexeme::*;
+pub use self::ll1::*;
+pub use self::tree::*;
@@ -0,0 +1,108 @@
+use crate::grammar::{NonTerminal, Production, Symbol, Terminal};
+use crate::parser::Lexeme;
+use std::fmt;
+
+/// Parse tree of a sequence of lexemes.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseTree {
+    /// Nonterminal expanded with a production.
+    Node {
+        /// The expanded nonterminal.
+        nonterminal: NonTerminal,
+        /// Index of the production in [crate::grammar::Grammar::productions].
+        production: usize,
+        /// One subtree for each symbol of the right-hand side of the production.
+        children: Vec<ParseTree>,
+    },
+    /// Terminal matched by a lexeme.
+    Leaf {
+        /// The matched terminal.
+        terminal: Terminal,
+        /// Text of the lexeme.
+        text: String,
+    },
+}
+
+impl ParseTree {
+    /// Builds the parse tree of a leftmost derivation of `lexemes`, given as production indices.
+    /// Returns [None] if the derivation does not match the productions or the lexemes.
+    pub fn from_derivation(
+        productions: &[Production],
+        derivation: &[usize],
+        lexemes: &[Lexeme],
+    ) -> Option<Self> {
+        let mut derivation = derivation.iter().copied();
+        let mut lexemes = lexemes.iter();
+        let tree = Self::expand(productions, &mut derivation, &mut lexemes)?;
+
+        match (derivation.next(), lexemes.next()) {
+            (None, None) => Some(tree),
+            _ => None,
+        }
+    }
+
+    /// Builds the subtree of the next production of `derivation`.
+    fn expand<'a, D, L>(
+        productions: &[Production],
+        derivation: &mut D,
+        lexemes: &mut L,
+    ) -> Option<Self>
+    where
+        D: Iterator<Item = usize>,
+        L: Iterator<Item = &'a Lexeme>,
+    {
+        let production = derivation.next()?;
+        let nonterminal = productions.get(production)?.nonterminal()?.clone();
+        let mut children = Vec::new();
+
+        for symbol in productions[production].rhs.iter() {
+            children.push(match symbol {
+                Symbol::Terminal(terminal) => {
+                    Self::Leaf { terminal: terminal.clone(), text: lexemes.next()?.text.clone() }
+                }
+                Symbol::NonTerminal(_) => Self::expand(productions, derivation, lexemes)?,
+            });
+        }
+
+        Some(Self::Node { nonterminal, production, children })
+    }
+
+    /// Returns the text of the leaves from left to right, which is the parsed input.
+    pub fn leaves(&self) -> Vec<&str> {
+        match self {
+            Self::Node { children, .. } => children.iter().flat_map(Self::leaves).collect(),
+            Self::Leaf { text, .. } => vec![text.as_str()],
+        }
+    }
+
+    /// Writes the tree with one line per node, indenting children by two spaces.
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = depth * 2;
+
+        match self {
+            Self::Node { nonterminal, children, .. } => {
+                writeln!(f, "{:indent$}{}", "", nonterminal, indent = indent)?;
+
+                if children.is_empty() {
+                    writeln!(f, "{:indent$}(nothing)", "", indent = indent + 2)?;
+                }
+
+                for child in children.iter() {
+                    child.write_indented(f, depth + 1)?;
+                }
+
+                Ok(())
+            }
+            Self::Leaf { terminal: Terminal::Token(name), text } => {
+                writeln!(f, "{:indent$}<{}> {}", "", name, text, indent = indent)
+            }
+            Self::Leaf { text, .. } => writeln!(f, "{:indent$}{}", "", text, indent = indent),
+        }
+    }
+}
+
+impl fmt::Display for ParseTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
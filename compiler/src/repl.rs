@@ -0,0 +1,493 @@
+use compiler::state_machine::{StateMachine, StateMachineFormat, Tag, Transducer};
+use compiler::utils::Rng;
+use std::error::Error;
+use std::io::Write;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Maximum number of scripts running each other at the same time.
+const MAX_SCRIPT_DEPTH: usize = 16;
+
+/// Commands listed by `help`, as pairs of usages and descriptions.
+const COMMANDS: [(&str, &str); 28] = [
+    ("help", "Show this list of commands"),
+    ("display", "Print the states, alphabet, transitions, initial and final states"),
+    ("table", "Print the transition function as a grid of states and symbols"),
+    ("validate <sequence|symbols...>", "Check whether the sequence is accepted"),
+    ("analyze", "Report unreachable and dead states, missing transitions and language size"),
+    ("trace <sequence|symbols...>", "Print every transition taken on the sequence"),
+    ("translate <sequence|symbols...>", "Print the outputs of a transducer on the sequence"),
+    ("regex", "Print an equivalent regular expression"),
+    ("enumerate <max_len>", "Print the accepted sequences up to the given length"),
+    ("sample <len> [seed]", "Print a random accepted sequence of the given length"),
+    ("add-state <state>", "Add a state"),
+    ("add-transition <src> <symbol> <dst>", "Add or replace a transition"),
+    ("set-final <state> [true|false]", "Mark a state as final or non-final"),
+    ("set-tag <state> [name [priority]]", "Tag a final state, or remove its tag"),
+    ("classify <sequence|symbols...>", "Print the tag of the final state reached"),
+    ("load <file>", "Replace the state machine with the one in the file"),
+    ("save <file>", "Save the state machine, in the format given by the file extension"),
+    ("dot <file>", "Write the state machine as a Graphviz DOT graph"),
+    ("union <file>", "Replace the state machine with its union with another one"),
+    ("intersection <file>", "Replace the state machine with its intersection with another one"),
+    ("difference <file>", "Replace the state machine with its difference with another one"),
+    ("complement [symbols...]", "Replace the state machine with its complement"),
+    ("history", "Print the commands entered so far"),
+    ("!<n>", "Run the n-th command from the history again"),
+    ("run <file>", "Run the commands in the file, one per line"),
+    ("exit", "Exit the REPL"),
+    ("", "Arguments containing spaces can be quoted, as in \"a b\""),
+    ("", "Lines starting with # are ignored"),
+];
+
+/// Interactive session editing and querying a single state machine.
+pub struct Repl {
+    /// The current state machine.
+    state_machine: StateMachine,
+    /// The transducer described by the loaded file, or the reason there is none.
+    transducer: Result<Transducer, String>,
+    /// Commands entered so far.
+    history: Vec<String>,
+    /// Number of scripts currently running.
+    script_depth: usize,
+    /// Whether the session should continue.
+    should_run: bool,
+}
+
+impl Repl {
+    /// Creates a session for `state_machine`, which may also be a transducer.
+    pub fn new(state_machine: StateMachine, transducer: Result<Transducer, String>) -> Self {
+        Self { state_machine, transducer, history: Vec::new(), script_depth: 0, should_run: true }
+    }
+
+    /// Reads and runs commands from stdin until `exit` or the end of the input.
+    pub fn run(&mut self) {
+        let mut input = String::new();
+
+        while self.should_run {
+            print!(">>> ");
+            std::io::stdout().flush().expect("Failed to flush stdout");
+
+            input.clear();
+            let read = std::io::stdin().read_line(&mut input).expect("Failed to read from stdin");
+
+            if read == 0 {
+                println!();
+                break;
+            }
+
+            let line = input.trim();
+
+            match line.strip_prefix('!').map(str::parse::<usize>) {
+                Some(Ok(n)) => match n.checked_sub(1).and_then(|i| self.history.get(i)).cloned() {
+                    Some(command) => {
+                        println!("{}", command);
+                        self.history.push(command.clone());
+                        self.execute_line(&command);
+                    }
+                    None => {
+                        eprintln!("No command number {} in the history", n);
+                        println!();
+                    }
+                },
+                Some(Err(error)) => {
+                    eprintln!("Invalid history number: {}", error);
+                    println!();
+                }
+                None => {
+                    if !line.is_empty() {
+                        self.history.push(line.to_owned());
+                    }
+
+                    self.execute_line(line);
+                }
+            }
+        }
+    }
+
+    /// Runs a single line of input, followed by an empty line.
+    fn execute_line(&mut self, line: &str) {
+        if line.starts_with('#') {
+            return;
+        }
+
+        match split_arguments(line) {
+            Ok(args) if args.is_empty() => (),
+            Ok(args) => self.execute(&args),
+            Err(error) => eprintln!("{}", error),
+        }
+
+        println!();
+    }
+
+    /// Runs the command made of `args`, the first argument being the command name.
+    fn execute(&mut self, args: &[String]) {
+        let arg = |i: usize| args.get(i).map(String::as_str);
+
+        match args[0].as_str() {
+            "help" => {
+                let width = COMMANDS.iter().map(|(usage, _)| usage.len()).max().unwrap_or(0);
+
+                for (usage, description) in COMMANDS {
+                    println!("{:width$}  {}", usage, description, width = width);
+                }
+            }
+            "display" => display_state_machine(&self.state_machine),
+            "table" => print!("{}", self.state_machine.to_table_string()),
+            "validate" => match self.sequence_argument(args) {
+                Some(sequence) => {
+                    let display_sequence = sequence.concat();
+
+                    if self.state_machine.is_accepted(&sequence) {
+                        println!("{} is accepted", display_sequence);
+                    } else {
+                        println!("{} is not accepted", display_sequence);
+                    }
+                }
+                None => eprintln!("No sequence provided"),
+            },
+            "analyze" => println!("{}", self.state_machine.analyze()),
+            "trace" => match self.sequence_argument(args) {
+                Some(sequence) => println!("{}", self.state_machine.trace(&sequence)),
+                None => eprintln!("No sequence provided"),
+            },
+            "translate" => match (&self.transducer, self.sequence_argument(args)) {
+                (Ok(transducer), Some(sequence)) => {
+                    println!("{}", transducer.translate(&sequence))
+                }
+                (Err(error), Some(_)) => eprintln!("Not a transducer: {}", error),
+                (_, None) => eprintln!("No sequence provided"),
+            },
+            "regex" => println!("{}", self.state_machine.to_regexp()),
+            "enumerate" => match arg(1).map(str::parse::<usize>) {
+                Some(Ok(max_len)) => {
+                    for sequence in self.state_machine.enumerate(max_len) {
+                        println!("\"{}\"", sequence.concat());
+                    }
+                }
+                Some(Err(error)) => eprintln!("Invalid length: {}", error),
+                None => eprintln!("No length provided"),
+            },
+            "sample" => match arg(1).map(str::parse::<usize>) {
+                Some(Ok(len)) => {
+                    let mut rng = match arg(2).map(str::parse::<u64>) {
+                        Some(Ok(seed)) => Rng::new(seed),
+                        Some(Err(error)) => {
+                            eprintln!("Invalid seed: {}", error);
+                            return;
+                        }
+                        None => Rng::from_time(),
+                    };
+
+                    match self.state_machine.sample(len, &mut rng) {
+                        Some(sequence) => println!("\"{}\"", sequence.concat()),
+                        None if self.state_machine.count_accepted(len)[len] != 0 => {
+                            eprintln!("Too many sequences of length {} to sample from", len)
+                        }
+                        None => println!("No sequence of length {} is accepted", len),
+                    }
+                }
+                Some(Err(error)) => eprintln!("Invalid length: {}", error),
+                None => eprintln!("No length provided"),
+            },
+            "add-state" => match arg(1) {
+                Some(state) => {
+                    if self.state_machine.add_state(state) {
+                        self.modified();
+                        println!("Added state {:?}", state);
+                    } else {
+                        eprintln!("State {:?} already exists", state);
+                    }
+                }
+                None => eprintln!("No state provided"),
+            },
+            "add-transition" => match (arg(1), arg(2), arg(3)) {
+                (Some(src_state), Some(symbol), Some(dst_state)) => {
+                    match self.state_machine.add_transition(src_state, symbol, dst_state) {
+                        Ok(previous) => {
+                            self.modified();
+                            println!("Added {} ---({})--> {}", src_state, symbol, dst_state);
+
+                            if let Some(previous) = previous {
+                                println!("Replaced {} ---({})--> {}", src_state, symbol, previous);
+                            }
+                        }
+                        Err(error) => eprintln!("Failed to add transition: {}", error),
+                    }
+                }
+                _ => eprintln!("A source state, a symbol and a destination state must be provided"),
+            },
+            "set-final" => {
+                let is_final = match arg(2) {
+                    Some("true") | None => true,
+                    Some("false") => false,
+                    Some(unknown) => {
+                        eprintln!("Expected true or false, got '{}'", unknown);
+                        return;
+                    }
+                };
+
+                match arg(1) {
+                    Some(state) => match self.state_machine.set_final(state, is_final) {
+                        Ok(()) => {
+                            self.modified();
+
+                            if is_final {
+                                println!("{:?} is now final", state);
+                            } else {
+                                println!("{:?} is now non-final", state);
+                            }
+                        }
+                        Err(error) => eprintln!("Failed to update state: {}", error),
+                    },
+                    None => eprintln!("No state provided"),
+                }
+            }
+            "set-tag" => {
+                let tag = match (arg(2), arg(3).map(str::parse::<i32>)) {
+                    (Some(name), None) => Some(Tag::new(name, 0)),
+                    (Some(name), Some(Ok(priority))) => Some(Tag::new(name, priority)),
+                    (Some(_), Some(Err(error))) => {
+                        eprintln!("Invalid priority: {}", error);
+                        return;
+                    }
+                    (None, _) => None,
+                };
+
+                match arg(1) {
+                    Some(state) => match self.state_machine.set_tag(state, tag.clone()) {
+                        Ok(()) => {
+                            self.modified();
+
+                            match tag {
+                                Some(tag) => println!("{:?} is now tagged {}", state, tag),
+                                None => println!("{:?} is now untagged", state),
+                            }
+                        }
+                        Err(error) => eprintln!("Failed to update state: {}", error),
+                    },
+                    None => eprintln!("No state provided"),
+                }
+            }
+            "classify" => match self.sequence_argument(args) {
+                Some(sequence) => match self.state_machine.classify(&sequence) {
+                    Some(tag) => println!("{} is classified as {}", sequence.concat(), tag),
+                    None => println!("{} is not classified", sequence.concat()),
+                },
+                None => eprintln!("No sequence provided"),
+            },
+            "load" => match arg(1) {
+                Some(path) => match load_file(path) {
+                    Ok((state_machine, transducer)) => {
+                        self.state_machine = state_machine;
+                        self.transducer = transducer;
+                        println!("Loaded state machine from {}", path);
+                    }
+                    Err(error) => eprintln!("Failed to load state machine: {}", error),
+                },
+                None => eprintln!("No file provided"),
+            },
+            "save" => match arg(1) {
+                Some(save_path) => {
+                    let format = StateMachineFormat::from_path(save_path);
+
+                    match std::fs::write(
+                        save_path,
+                        self.state_machine.to_string_with_format(format),
+                    ) {
+                        Ok(()) => println!("Saved state machine to {}", save_path),
+                        Err(error) => eprintln!("Failed to save state machine: {}", error),
+                    }
+                }
+                None => eprintln!("No file provided"),
+            },
+            "dot" => match arg(1) {
+                Some(dot_path) => match std::fs::write(dot_path, self.state_machine.to_dot()) {
+                    Ok(()) => println!("Wrote DOT graph to {}", dot_path),
+                    Err(error) => eprintln!("Failed to write DOT file: {}", error),
+                },
+                None => eprintln!("No DOT file provided"),
+            },
+            command @ ("union" | "intersection" | "difference") => match arg(1) {
+                Some(other_path) => match load_file(other_path) {
+                    Ok((other, _)) => {
+                        self.state_machine = match command {
+                            "union" => self.state_machine.union(&other),
+                            "intersection" => self.state_machine.intersection(&other),
+                            _ => self.state_machine.difference(&other),
+                        };
+
+                        self.modified();
+                        println!("Replaced state machine with the {}", command);
+                    }
+                    Err(error) => eprintln!("Failed to load state machine: {}", error),
+                },
+                None => eprintln!("No file provided"),
+            },
+            "complement" => {
+                self.state_machine = self.state_machine.complement(&args[1..]);
+                self.modified();
+                println!("Replaced state machine with its complement");
+            }
+            "history" => {
+                for (i, command) in self.history.iter().enumerate() {
+                    println!("{:4}  {}", i + 1, command);
+                }
+            }
+            "run" => match arg(1) {
+                Some(script_path) => self.run_script(script_path),
+                None => eprintln!("No script file provided"),
+            },
+            "exit" => self.should_run = false,
+            unknown => eprintln!("Unknown command: '{}'", unknown),
+        }
+    }
+
+    /// Returns the sequence given after the command name. A single argument is split into
+    /// grapheme clusters, unless it is a symbol of the alphabet. Several arguments are taken as
+    /// one symbol each, so that `validate "a b" c` reads the symbols `a b` and `c`.
+    fn sequence_argument<'a>(&self, args: &'a [String]) -> Option<Vec<&'a str>> {
+        match &args[1..] {
+            [] => None,
+            [sequence] if !self.state_machine.iter_symbols().any(|symbol| symbol == sequence) => {
+                Some(sequence.graphemes(true).collect())
+            }
+            symbols => Some(symbols.iter().map(String::as_str).collect()),
+        }
+    }
+
+    /// Runs the commands in the file at `path`, echoing each of them.
+    fn run_script(&mut self, path: &str) {
+        if self.script_depth == MAX_SCRIPT_DEPTH {
+            eprintln!("Scripts are nested too deeply");
+            return;
+        }
+
+        let script = match std::fs::read_to_string(path) {
+            Ok(script) => script,
+            Err(error) => {
+                eprintln!("Failed to read script: {}", error);
+                return;
+            }
+        };
+
+        self.script_depth += 1;
+
+        for line in script.lines().map(str::trim) {
+            if !self.should_run {
+                break;
+            }
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            println!(">>> {}", line);
+            self.execute_line(line);
+        }
+
+        self.script_depth -= 1;
+    }
+
+    /// Drops the transducer after the state machine was changed, as its outputs may no longer
+    /// match the states and transitions.
+    fn modified(&mut self) {
+        self.transducer = Err("The state machine was modified".to_owned());
+    }
+}
+
+/// Loads a state machine from the file at `path`, along with the transducer it describes.
+pub fn load_file(path: &str) -> Result<(StateMachine, Result<Transducer, String>), Box<dyn Error>> {
+    let input = std::fs::read_to_string(path)?;
+    let format = StateMachineFormat::from_path(path);
+    let state_machine = StateMachine::parse(&input, format)?;
+
+    Ok((state_machine, parse_transducer(&input, format)))
+}
+
+/// Parses the transducer described by `input`. Transducers extend the JSON format, so any
+/// state machine file may also describe one.
+pub fn parse_transducer(input: &str, format: StateMachineFormat) -> Result<Transducer, String> {
+    match format {
+        StateMachineFormat::Json => {
+            serde_json::from_str::<Transducer>(input).map_err(|error| error.to_string())
+        }
+        StateMachineFormat::Fa => Err("Transducers can only be loaded from JSON files".to_owned()),
+    }
+}
+
+/// Splits a line into whitespace-separated arguments. Double quotes group characters,
+/// including whitespace, into a single argument, and `\` escapes the next character inside
+/// them.
+pub fn split_arguments(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut arg = None::<String>;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let arg = arg.get_or_insert_with(String::new);
+
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => arg.push(escaped),
+                            None => return Err("Unterminated escape sequence".to_owned()),
+                        },
+                        Some(c) => arg.push(c),
+                        None => return Err("Unterminated quoted argument".to_owned()),
+                    }
+                }
+            }
+            c if c.is_whitespace() => args.extend(arg.take()),
+            c => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    args.extend(arg);
+    Ok(args)
+}
+
+fn display_state_machine(state_machine: &StateMachine) {
+    println!("[STATES]");
+    for state in state_machine.iter_states() {
+        println!("{}", state);
+    }
+
+    println!("\n[ALPHABET]");
+    for symbol in state_machine.iter_symbols() {
+        println!("{}", symbol);
+    }
+
+    println!("\n[TRANSITIONS]");
+    for (src_state, symbol, dst_state) in state_machine.iter_transitions() {
+        println!("{} ---({})--> {}", src_state, symbol, dst_state);
+    }
+
+    println!("\n[INITIAL STATE]");
+    println!("{}", state_machine.initial_state());
+
+    println!("\n[FINAL STATES]");
+    for final_state in state_machine.final_states() {
+        match state_machine.tag(final_state) {
+            Some(tag) => println!("{}: {}", final_state, tag),
+            None => println!("{}", final_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_arguments() {
+        assert_eq!(split_arguments("  validate  12 ").unwrap(), ["validate", "12"]);
+        assert_eq!(split_arguments(r#"trace "a b""#).unwrap(), ["trace", "a b"]);
+        assert_eq!(split_arguments(r#"validate """#).unwrap(), ["validate", ""]);
+        assert_eq!(split_arguments(r#"x"y z"\"#).unwrap(), [r#"xy z\"#]);
+        assert_eq!(split_arguments(r#""\"\\""#).unwrap(), [r#""\"#]);
+        assert!(split_arguments(r#"trace "a"#).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 mod error;
+#[allow(clippy::module_inception)]
 mod scanner;
 mod tokens;
 
@@ -1,7 +1,5 @@
 use crate::scanner::{LexicalError, Operator, ReservedWord, Separator, Token};
-use crate::state_machine::StateMachine;
 use crate::symbols::{Const, Symbol, SymbolTable};
-use crate::utils;
 use lazy_static::lazy_static;
 use regex::Regex;
 use unicode_segmentation::UnicodeSegmentation;
@@ -19,24 +17,16 @@ lazy_static! {
     /// Matches identifiers that start with underscores or ascii letters.
     static ref IDENT_REGEX: Regex
         = Regex::new(r"(^(_[_a-zA-Z0-9]+)$|^(([a-zA-Z])[_a-zA-Z0-9]*)$)").unwrap();
+}
 
-    /// State machine for validating numeric literals
-    static ref NUMBER_STATE_MACHINE: StateMachine = {
-        let number_state_machine = std::fs::read_to_string("state-machines/number.json")
-            .expect("Failed to read number state machine file");
-
-        serde_json::from_str::<StateMachine>(&number_state_machine)
-            .expect("Failed to parse number state machine file")
-    };
-
-    /// State machine for validating identifiers.
-    static ref IDENT_STATE_MACHINE: StateMachine = {
-        let ident_string = std::fs::read_to_string("state-machines/identifier.json")
-            .expect("Failed to read identifier state machine file");
+/// State machine for validating numeric literals, generated from `state-machines/number.json`.
+mod number_state_machine {
+    include!(concat!(env!("OUT_DIR"), "/number.rs"));
+}
 
-        serde_json::from_str::<StateMachine>(&ident_string)
-            .expect("Failed to parse identifier state machine file")
-    };
+/// State machine for validating identifiers, generated from `state-machines/identifier.json`.
+mod ident_state_machine {
+    include!(concat!(env!("OUT_DIR"), "/identifier.rs"));
 }
 
 /// Source file split into its tokens, identifiers and constants.
@@ -59,17 +49,21 @@ impl Program {
         let mut word = String::new();
         let mut operator_string = String::new();
 
+        // Both machines are fed each grapheme of the current word as it is read.
+        let mut number_state = number_state_machine::INITIAL_STATE;
+        let mut ident_state = ident_state_machine::INITIAL_STATE;
+
         fn consume_word(
             program: &mut Program,
             word: &mut String,
+            number_state: &mut u32,
+            ident_state: &mut u32,
             line: usize,
         ) -> Result<(), LexicalError> {
             if let Some(reserved_word) = ReservedWord::try_parse(word) {
                 program.tokens.push(reserved_word.into());
             } else if !word.is_empty() {
-                let sequence = utils::str_to_grapheme_clusters(word);
-
-                if NUMBER_STATE_MACHINE.is_accepted(&sequence) {
+                if number_state_machine::is_final(*number_state) {
                     let number = parse_i32(word);
                     let number_id = program.consts.insert(Const::I32(number).into());
                     program.tokens.push(Token::Literal(number_id));
@@ -85,7 +79,7 @@ impl Program {
                     let char_literal = char_literal.as_str().chars().next().unwrap_or('\0');
                     let char_literal_id = program.consts.insert(Const::Char(char_literal).into());
                     program.tokens.push(Token::Literal(char_literal_id));
-                } else if IDENT_STATE_MACHINE.is_accepted(&sequence) {
+                } else if ident_state_machine::is_final(*ident_state) {
                     let ident_id = program.idents.insert(Symbol::Ident(word.clone()));
                     program.tokens.push(Token::Ident(ident_id));
                 } else {
@@ -94,18 +88,20 @@ impl Program {
             }
 
             word.clear();
+            *number_state = number_state_machine::INITIAL_STATE;
+            *ident_state = ident_state_machine::INITIAL_STATE;
             Ok(())
         }
 
         while let Some(grapheme) = grapheme_iter.next() {
             if let Some(separator) = Separator::try_parse(grapheme) {
-                consume_word(&mut program, &mut word, line)?;
+                consume_word(&mut program, &mut word, &mut number_state, &mut ident_state, line)?;
 
                 if !separator.is_whitespace() {
                     program.tokens.push(separator.into());
                 }
             } else if let Some(operator) = Operator::try_parse(grapheme) {
-                consume_word(&mut program, &mut word, line)?;
+                consume_word(&mut program, &mut word, &mut number_state, &mut ident_state, line)?;
 
                 if operator.needs_lookahead() {
                     operator_string.clear();
@@ -123,6 +119,8 @@ impl Program {
                 }
             } else {
                 word.push_str(grapheme);
+                number_state = number_state_machine::step(number_state, grapheme);
+                ident_state = ident_state_machine::step(ident_state, grapheme);
             }
 
             if grapheme == "\n" {
@@ -157,7 +155,7 @@ pub fn parse_i32(input: &str) -> i32 {
     let mut accumulator = 0;
 
     for digit in input.chars() {
-        if !('0'..='9').contains(&digit) {
+        if !digit.is_ascii_digit() {
             panic!("Failed to parse i32");
         }
 
@@ -173,6 +171,7 @@ pub fn parse_i32(input: &str) -> i32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::state_machine::StateMachine;
 
     #[test]
     fn test_parse_i32() {
@@ -180,4 +179,74 @@ mod tests {
         assert_eq!(parse_i32("123"), 123);
         assert_eq!(parse_i32("1000"), 1000);
     }
+
+    /// Generated module of a state machine, as its initial state, state names and functions.
+    type Module = (u32, &'static [&'static str], fn(u32, &str) -> u32, fn(&str) -> bool);
+
+    #[test]
+    fn test_generated_modules_match_state_machines() {
+        let modules: [(&str, Module); 2] = [
+            (
+                include_str!("../../state-machines/number.json"),
+                (
+                    number_state_machine::INITIAL_STATE,
+                    &number_state_machine::STATE_NAMES,
+                    number_state_machine::step,
+                    number_state_machine::accepts,
+                ),
+            ),
+            (
+                include_str!("../../state-machines/identifier.json"),
+                (
+                    ident_state_machine::INITIAL_STATE,
+                    &ident_state_machine::STATE_NAMES,
+                    ident_state_machine::step,
+                    ident_state_machine::accepts,
+                ),
+            ),
+        ];
+
+        for (json, (initial_state, state_names, step, accepts)) in modules {
+            let state_machine = serde_json::from_str::<StateMachine>(json).unwrap();
+
+            // Every sequence of up to 5 symbols, including one outside of the alphabet.
+            let mut symbols = state_machine.sorted_symbols();
+            symbols.push("x");
+
+            let mut level = vec![Vec::<&str>::new()];
+            let mut sequences = level.clone();
+
+            for _ in 0..5 {
+                level = level
+                    .iter()
+                    .flat_map(|sequence| {
+                        symbols.iter().map(move |&symbol| [sequence.as_slice(), &[symbol]].concat())
+                    })
+                    .collect();
+                sequences.extend(level.iter().cloned());
+            }
+
+            for sequence in sequences {
+                let mut state = Some(state_machine.initial_state());
+                let mut id = initial_state;
+
+                for &symbol in sequence.iter() {
+                    state = state.and_then(|state| state_machine.next_state(state, symbol));
+                    id = step(id, symbol);
+                    assert_eq!(state_names.get(id as usize).copied(), state, "{:?}", sequence);
+                }
+
+                let accepted = state_machine.is_accepted(&sequence);
+                assert_eq!(accepts(&sequence.concat()), accepted, "{:?}", sequence);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generated_state_machines() {
+        assert!(number_state_machine::accepts("120"));
+        assert!(!number_state_machine::accepts("012"));
+        assert!(ident_state_machine::accepts("_a1"));
+        assert!(!ident_state_machine::accepts("1a"));
+    }
 }
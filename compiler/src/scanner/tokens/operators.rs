@@ -39,6 +39,17 @@ impl Operator {
         None
     }
 
+    /// Returns the text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Assignment(operator) => operator.as_str(),
+            Self::Arithmetic(operator) => operator.as_str(),
+            Self::Relational(operator) => operator.as_str(),
+            Self::Logical(operator) => operator.as_str(),
+            Self::Bitwise(operator) => operator.as_str(),
+        }
+    }
+
     /// Returns `true` if the scanner needs to look ahead to determine the correct operator type.
     pub fn needs_lookahead(&self) -> bool {
         match self {
@@ -94,6 +105,11 @@ impl AssignmentOperator {
         })
     }
 
+    /// Returns the text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        "="
+    }
+
     /// Returns `true` if the scanner needs to look ahead to determine the correct operator type.
     pub fn needs_lookahead(&self) -> bool {
         // = -> ==
@@ -129,6 +145,17 @@ impl ArithmeticOperator {
         })
     }
 
+    /// Returns the text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Addition => "+",
+            Self::Subtraction => "-",
+            Self::Multiplication => "*",
+            Self::Division => "/",
+            Self::Remainder => "%",
+        }
+    }
+
     /// Returns `true` if the scanner needs to look ahead to determine the correct operator type.
     pub fn needs_lookahead(&self) -> bool {
         false
@@ -166,6 +193,18 @@ impl RelationalOperator {
         })
     }
 
+    /// Returns the text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::LessOrEqual => "<=",
+            Self::Greater => ">",
+            Self::GreaterOrEqual => ">=",
+        }
+    }
+
     /// Returns `true` if the scanner needs to look ahead to determine the correct operator type.
     pub fn needs_lookahead(&self) -> bool {
         // < -> <=, > -> >=
@@ -195,6 +234,15 @@ impl LogicalOperator {
         })
     }
 
+    /// Returns the text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::And => "&&",
+            Self::Or => "||",
+            Self::Not => "!",
+        }
+    }
+
     /// Returns `true` if the scanner needs to look ahead to determine the correct operator type.
     pub fn needs_lookahead(&self) -> bool {
         // ! -> !=
@@ -224,6 +272,15 @@ impl BitwiseOperator {
         })
     }
 
+    /// Returns the text of the operator.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::And => "&",
+            Self::Or => "|",
+            Self::Not => "^",
+        }
+    }
+
     /// Returns `true` if the scanner needs to look ahead to determine the correct operator type.
     pub fn needs_lookahead(&self) -> bool {
         // & -> &&, | -> ||
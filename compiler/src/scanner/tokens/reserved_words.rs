@@ -26,6 +26,15 @@ impl ReservedWord {
 
         None
     }
+
+    /// Returns the text of the reserved word.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Primitive(primitive) => primitive.as_str(),
+            Self::ControlFlow(control_flow) => control_flow.as_str(),
+            Self::BoolLiteral(bool_literal) => bool_literal.as_str(),
+        }
+    }
 }
 
 /// Primitive type defined by the "cool language" specification.
@@ -52,6 +61,16 @@ impl Primitive {
             _ => return None,
         })
     }
+
+    /// Returns the text of the primitive.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::I32 => "i32",
+            Self::Bool => "bool",
+            Self::Char => "char",
+            Self::Str => "str",
+        }
+    }
 }
 
 /// Control flow word defined by the "cool language" specification.
@@ -81,6 +100,17 @@ impl ControlFlow {
             _ => return None,
         })
     }
+
+    /// Returns the text of the control flow word.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Else => "else",
+            Self::For => "for",
+            Self::In => "in",
+            Self::While => "while",
+        }
+    }
 }
 
 /// Boolean literal defined by the "cool language" specification.
@@ -101,4 +131,12 @@ impl BoolLiteral {
             _ => return None,
         })
     }
+
+    /// Returns the text of the boolean literal.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::True => "true",
+            Self::False => "false",
+        }
+    }
 }
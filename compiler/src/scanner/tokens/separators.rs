@@ -41,6 +41,21 @@ impl Separator {
         }
     }
 
+    /// Returns the text of the separator, which is a single space for whitespace.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Whitespace => " ",
+            Self::Semicolon => ";",
+            Self::Colon => ":",
+            Self::OpenParanthesis => "(",
+            Self::ClosedParanthesis => ")",
+            Self::OpenSquareBracket => "[",
+            Self::ClosedSquareBracket => "]",
+            Self::OpenBracket => "{",
+            Self::ClosedBracket => "}",
+        }
+    }
+
     /// Returns whether the separator is whitespace.
     pub fn is_whitespace(&self) -> bool {
         matches!(self, Self::Whitespace)
@@ -66,6 +66,18 @@ impl SymbolTable {
         None
     }
 
+    /// Returns all symbols indexed by their code, for looking up many codes at once. Codes start
+    /// at 1, so the first slot is always [None].
+    pub fn symbols_by_code(&self) -> Vec<Option<&Symbol>> {
+        let mut symbols = vec![None; self.len + 1];
+
+        for (symbol, code) in self.iter() {
+            symbols[*code] = Some(symbol);
+        }
+
+        symbols
+    }
+
     /// Returns whether the table contains a symbol.
     #[inline]
     pub fn contains(&self, symbol: &Symbol) -> bool {
@@ -74,7 +86,7 @@ impl SymbolTable {
 
     /// Returns an iterator over all symbols in the table and their associated codes.
     #[inline]
-    pub fn iter(&self) -> SymbolTableIter {
+    pub fn iter(&self) -> SymbolTableIter<'_> {
         SymbolTableIter(self.values.iter().flatten())
     }
 
@@ -111,7 +123,7 @@ impl SymbolTable {
 
     /// Returns whether the table should grow before inserting a new elements.
     fn should_grow(&self) -> bool {
-        if self.values.len() == 0 {
+        if self.values.is_empty() {
             true
         } else {
             (self.len + 1) as f64 / self.slots() as f64 > SYMBOL_TABLE_MAX_LOAD_FACTOR
@@ -179,5 +191,11 @@ mod tests {
         assert_eq!(symbol_table.len(), 2);
         assert!(symbol_table.contains(&s1));
         assert!(symbol_table.contains(&s2));
+
+        // Look symbols up by code
+        let symbols = symbol_table.symbols_by_code();
+        assert_eq!(symbols.len(), 3);
+        assert_eq!(symbols[c1], Some(&s1));
+        assert_eq!(symbols[c2], Some(&s2));
     }
 }